@@ -0,0 +1,94 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde_json::Value;
+
+use crate::{
+    error::{Error, Result},
+    solution::Answer,
+};
+
+pub const ANSWERS_FILE: &str = "answers.json";
+
+/// Answers that are known to be right, keyed by day and part. The file is a
+/// JSON object of days holding objects of parts:
+///
+/// ```json
+/// { "1": { "1": 67016, "2": 200116 }, "5": { "1": "SHQWSRBDL" } }
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers(BTreeMap<(u8, u8), Answer>);
+
+impl Answers {
+    pub fn read(path: &Path) -> Result<Self> {
+        let name = path.display().to_string();
+        let text = fs::read_to_string(path).map_err(|source| Error::Io {
+            name: name.clone(),
+            source,
+        })?;
+
+        Answers::parse(&text).map_err(|message| Error::Answers { name, message })
+    }
+
+    pub fn parse(text: &str) -> std::result::Result<Self, String> {
+        let days = match serde_json::from_str(text).map_err(|error| error.to_string())? {
+            Value::Object(days) => days,
+            _ => return Err("expected an object of days".to_string()),
+        };
+
+        let mut answers = BTreeMap::new();
+
+        for (day, parts) in days {
+            let day = day.parse().map_err(|_| format!("invalid day {:?}", day))?;
+            let parts = match parts {
+                Value::Object(parts) => parts,
+                _ => return Err(format!("expected an object of parts for day {}", day)),
+            };
+
+            for (part, answer) in parts {
+                let part = part
+                    .parse()
+                    .map_err(|_| format!("invalid part {:?}", part))?;
+                let answer = match answer {
+                    Value::Number(number) => number.as_i64().map(Answer::Number),
+                    Value::String(text) => Some(Answer::Text(text)),
+                    _ => None,
+                }
+                .ok_or_else(|| format!("invalid answer for day {} part {}", day, part))?;
+
+                answers.insert((day, part), answer);
+            }
+        }
+
+        Ok(Answers(answers))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.0.get(&(day, part))
+    }
+}
+
+#[test]
+fn test_parse() {
+    let answers =
+        Answers::parse(r#"{ "1": { "1": 24000, "2": 45000 }, "5": { "1": "CMZ" } }"#).unwrap();
+
+    assert_eq!(answers.get(1, 2), Some(&Answer::Number(45000)));
+    assert_eq!(answers.get(5, 1), Some(&Answer::Text("CMZ".to_string())));
+    assert_eq!(answers.get(5, 2), None);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Answers::parse(r#"{ "one": { "1": 1 } }"#).unwrap_err(),
+        "invalid day \"one\""
+    );
+    assert_eq!(
+        Answers::parse(r#"{ "1": { "1": [1] } }"#).unwrap_err(),
+        "invalid answer for day 1 part 1"
+    );
+    assert_eq!(
+        Answers::parse("[]").unwrap_err(),
+        "expected an object of days"
+    );
+}
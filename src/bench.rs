@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::{
+    error::{Error, Result},
+    input::Input,
+    solution::Runnable,
+};
+
+/// Spread of the durations measured over the iterations of a benchmark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+
+    fn to_json(self) -> Value {
+        json!({
+            "min_ns": self.min.as_nanos() as u64,
+            "median_ns": self.median.as_nanos() as u64,
+            "max_ns": self.max.as_nanos() as u64,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Measurement {
+    pub day: u8,
+    pub part: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+impl Measurement {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "day": self.day,
+            "part": self.part,
+            "iterations": self.iterations,
+            "parse": self.parse.to_json(),
+            "solve": self.solve.to_json(),
+        })
+    }
+}
+
+/// Runs a part `iterations` times, parsing the input again every time so
+/// that both stages are measured on each iteration.
+pub fn measure(
+    solution: &dyn Runnable,
+    input: &Input,
+    part: u8,
+    iterations: usize,
+) -> Result<Measurement> {
+    if iterations == 0 {
+        return Err(Error::Usage("at least one iteration is needed".to_string()));
+    }
+
+    let mut parse = Vec::with_capacity(iterations);
+    let mut solve = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let (_, timing) = solution.run_timed(input, part)?;
+        parse.push(timing.parse);
+        solve.push(timing.solve);
+    }
+
+    Ok(Measurement {
+        day: solution.day(),
+        part,
+        iterations,
+        parse: Stats::new(parse),
+        solve: Stats::new(solve),
+    })
+}
+
+pub fn print_table(measurements: &[Measurement]) {
+    println!(
+        "{:>3}  {:>4}  {:<5}  {:>12}  {:>12}  {:>12}",
+        "Day", "Part", "Stage", "Min", "Median", "Max"
+    );
+
+    for measurement in measurements {
+        for (stage, stats) in [("parse", measurement.parse), ("solve", measurement.solve)] {
+            println!(
+                "{:>3}  {:>4}  {:<5}  {:>12}  {:>12}  {:>12}",
+                measurement.day,
+                measurement.part,
+                stage,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.max),
+            );
+        }
+    }
+}
+
+#[test]
+fn test_stats() {
+    let ms = Duration::from_millis;
+
+    assert_eq!(
+        Stats::new(vec![ms(3), ms(1), ms(2)]),
+        Stats {
+            min: ms(1),
+            median: ms(2),
+            max: ms(3)
+        }
+    );
+    assert_eq!(Stats::new(vec![ms(4), ms(1), ms(2), ms(9)]).median, ms(3));
+}
+
+#[test]
+fn test_measure() {
+    let day1 = crate::registry::find(1).unwrap();
+    let measurement = measure(day1, &crate::file::read_example(1), 2, 3).unwrap();
+
+    assert_eq!((measurement.day, measurement.part), (1, 2));
+    assert!(measurement.solve.min <= measurement.solve.median);
+    assert!(measurement.solve.median <= measurement.solve.max);
+    assert_eq!(measurement.to_json()["iterations"], 3);
+
+    assert!(measure(day1, &Input::from(""), 1, 0).is_err());
+}
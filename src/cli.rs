@@ -0,0 +1,998 @@
+use std::{
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde_json::{json, Value};
+
+use crate::{
+    answers::{Answers, ANSWERS_FILE},
+    bench,
+    day2::{self, Day2, Mapping, Report, Rules},
+    error::{Error, Result},
+    input::{Input, InputSource, INPUTS_DIR_VAR},
+    pool, registry,
+    server::{self, Server, BASE_URL_VAR, DEFAULT_BASE_URL, SESSION_VAR},
+    solution::{Answer, Runnable, Solution, Timing},
+    submit::{Attempt, History, HISTORY_FILE},
+};
+
+#[derive(Parser, Debug)]
+#[command(name = "aoc2022", about = "Advent of Code 2022 solutions")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    /// Read the puzzle input from this file instead of dayN.txt, or from stdin with -
+    #[arg(long, global = true)]
+    pub input: Option<PathBuf>,
+
+    /// Directory holding the dayN.txt puzzle inputs [default: current directory]
+    #[arg(long, global = true, env = INPUTS_DIR_VAR)]
+    pub inputs_dir: Option<PathBuf>,
+}
+
+impl Cli {
+    pub fn input_source(&self) -> InputSource {
+        InputSource::resolve(self.input.clone(), self.inputs_dir.clone())
+    }
+}
+
+/// The number of threads to run on with `--parallel`, if it is given.
+pub fn jobs(parallel: bool, jobs: Option<usize>) -> Option<usize> {
+    if !parallel {
+        return None;
+    }
+
+    Some(jobs.unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get())))
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run the solutions for a single day, or for every day with --all
+    Run {
+        /// Day to run
+        #[arg(required_unless_present = "all")]
+        day: Option<u8>,
+
+        /// Only run this part of the day
+        #[arg(short, long)]
+        part: Option<u8>,
+
+        /// Run every day
+        #[arg(long, conflicts_with = "day")]
+        all: bool,
+
+        /// How the answers are printed
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+
+        /// Show how long parsing and solving took, with the text format
+        #[arg(long)]
+        time: bool,
+
+        /// Run the days in parallel
+        #[arg(long)]
+        parallel: bool,
+
+        /// Number of threads to run the days on [default: number of CPUs]
+        #[arg(short, long, requires = "parallel")]
+        jobs: Option<usize>,
+    },
+    /// Check the answers of every day, or of a single day, against the
+    /// recorded ones
+    Verify {
+        /// Only verify this day
+        day: Option<u8>,
+
+        /// JSON file with the expected answers, keyed by day and part
+        #[arg(long, default_value = ANSWERS_FILE)]
+        answers: PathBuf,
+    },
+    /// Time parsing and solving of every day, or of a single day
+    Bench {
+        /// Only benchmark this day
+        day: Option<u8>,
+
+        /// Only benchmark this part of the day
+        #[arg(short, long)]
+        part: Option<u8>,
+
+        /// Number of times each part is run
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations: usize,
+
+        /// How the results are printed
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Download the puzzle input of a day into the inputs directory, unless
+    /// it is already there
+    Fetch {
+        /// Day to download
+        day: u8,
+
+        #[command(flatten)]
+        server: ServerArgs,
+    },
+    /// Submit the answer of a part to the puzzle server, unless it is known
+    /// to be wrong
+    Submit {
+        /// Day of the answer
+        day: u8,
+
+        /// Part of the answer
+        part: u8,
+
+        #[command(flatten)]
+        server: ServerArgs,
+
+        /// JSON file recording every submission
+        #[arg(long, default_value = HISTORY_FILE)]
+        history: PathBuf,
+    },
+    /// Run a day again every time its module or its input changes
+    Watch {
+        /// Day to watch
+        day: u8,
+
+        /// Root of the crate the day is in
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+
+        /// Milliseconds between checks for changes
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+    /// Generate and register the module for a new day, with empty example
+    /// and puzzle inputs
+    NewDay {
+        /// Day to generate
+        day: u8,
+
+        /// Root of the crate to add the day to
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+    /// Score the day 2 strategy guide under another reading of its symbols
+    Guide {
+        /// Symbols of the opponent's rock, paper and scissors
+        #[arg(long, default_value = "ABC")]
+        opponent: String,
+
+        /// Symbols of my rock, paper and scissors, for part 1
+        #[arg(long, default_value = "XYZ")]
+        hands: String,
+
+        /// Symbols of losing, drawing and winning, for part 2
+        #[arg(long, default_value = "XYZ")]
+        outcomes: String,
+
+        /// Try every order of my symbols, and keep the one that scores the
+        /// most or the least
+        #[arg(long, value_enum, conflicts_with = "stats")]
+        search: Option<Search>,
+
+        /// Break the score down by outcome and by opponent hand
+        #[arg(long)]
+        stats: bool,
+
+        /// How the statistics are printed
+        #[arg(long, value_enum, default_value_t, requires = "stats")]
+        format: Format,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct ServerArgs {
+    /// URL of the puzzle server, up to the year
+    #[arg(long, env = BASE_URL_VAR, default_value = DEFAULT_BASE_URL)]
+    pub base_url: String,
+
+    /// Value of the session cookie of a logged in user
+    #[arg(long, env = SESSION_VAR, hide_env_values = true)]
+    pub session: String,
+}
+
+impl ServerArgs {
+    pub fn server(&self) -> Server {
+        Server::new(&self.base_url, &self.session)
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human readable text
+    #[default]
+    Text,
+    /// JSON, one record per day and part
+    Json,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Search {
+    Max,
+    Min,
+}
+
+fn select(source: &InputSource, day: Option<u8>) -> Result<Vec<&'static dyn Runnable>> {
+    match day {
+        Some(day) => Ok(vec![registry::find(day).ok_or(Error::UnknownDay(day))?]),
+        None if source.is_single() => Err(Error::Usage(
+            "--input can only be used when running a single day".to_string(),
+        )),
+        None => Ok(registry::SOLUTIONS.to_vec()),
+    }
+}
+
+fn parts(part: Option<u8>) -> Result<Vec<u8>> {
+    match part {
+        None => Ok(vec![1, 2]),
+        Some(part @ 1..=2) => Ok(vec![part]),
+        Some(part) => Err(Error::UnknownPart(part)),
+    }
+}
+
+/// The answers of the parts of a day, or why its input could not be read.
+type DayResults = Result<Vec<(u8, Result<(Answer, Timing)>)>>;
+
+fn solve(source: &InputSource, solution: &dyn Runnable, parts: &[u8]) -> DayResults {
+    let input = source.read(solution.day())?;
+
+    Ok(parts
+        .iter()
+        .map(|&part| (part, solution.run_timed(&input, part)))
+        .collect())
+}
+
+/// Prints the results of a day, returning the time they took and how many
+/// parts failed. Failures are only counted with the JSON format, the text
+/// format stops at the first one.
+fn print_results(
+    day: u8,
+    results: DayResults,
+    parts: &[u8],
+    format: Format,
+    time: bool,
+) -> Result<(Duration, usize)> {
+    let mut summed = Duration::ZERO;
+    let mut failed = 0;
+
+    match format {
+        Format::Text => {
+            for (part, result) in results? {
+                let (answer, timing) = result?;
+                let answer = answer.to_string();
+                summed += timing.parse + timing.solve;
+
+                if answer.contains('\n') {
+                    println!("Day {} part {}:\n{}", day, part, answer);
+                } else {
+                    println!("Day {} part {}: {}", day, part, answer);
+                }
+
+                if time {
+                    println!("  parse {:.2?}, solve {:.2?}", timing.parse, timing.solve);
+                }
+            }
+        }
+        Format::Json => {
+            // Failures are part of the records so that one broken day does
+            // not hide the results of the others.
+            let results = match results {
+                Ok(results) => results
+                    .into_iter()
+                    .map(|(part, result)| (part, result.map_err(|error| error.to_string())))
+                    .collect(),
+                Err(error) => parts
+                    .iter()
+                    .map(|&part| (part, Err(error.to_string())))
+                    .collect::<Vec<_>>(),
+            };
+
+            for (part, result) in results {
+                match &result {
+                    Ok((_, timing)) => summed += timing.parse + timing.solve,
+                    Err(_) => failed += 1,
+                }
+
+                println!("{}", record(day, part, &result));
+            }
+        }
+    }
+
+    Ok((summed, failed))
+}
+
+/// Runs the selected days one after the other, or on `jobs` threads when it
+/// is set. Answers are printed in day order either way.
+pub fn run(
+    source: &InputSource,
+    day: Option<u8>,
+    part: Option<u8>,
+    format: Format,
+    time: bool,
+    jobs: Option<usize>,
+) -> Result<()> {
+    let selected = select(source, day)?;
+    let parts = parts(part)?;
+    let total = selected.len() * parts.len();
+    let mut failed = 0;
+
+    match jobs {
+        None => {
+            for solution in selected {
+                let results = solve(source, solution, &parts);
+                failed += print_results(solution.day(), results, &parts, format, time)?.1;
+            }
+        }
+        Some(jobs) => {
+            let start = Instant::now();
+            let results =
+                pool::map_in_order(&selected, jobs, |solution| solve(source, *solution, &parts));
+            let wall_clock = start.elapsed();
+
+            let mut summed = Duration::ZERO;
+            for (solution, results) in selected.iter().zip(results) {
+                let (duration, failures) =
+                    print_results(solution.day(), results, &parts, format, time)?;
+                summed += duration;
+                failed += failures;
+            }
+
+            eprintln!(
+                "Ran {} days on {} threads in {:.2?} wall-clock, {:.2?} summed over all parts",
+                selected.len(),
+                jobs.clamp(1, selected.len()),
+                wall_clock,
+                summed
+            );
+        }
+    }
+
+    if failed > 0 {
+        return Err(Error::Failed { failed, total });
+    }
+
+    Ok(())
+}
+
+/// A JSON record of a run, with the duration of parsing and solving in
+/// seconds.
+fn record(day: u8, part: u8, result: &std::result::Result<(Answer, Timing), String>) -> Value {
+    match result {
+        Ok((answer, timing)) => json!({
+            "day": day,
+            "part": part,
+            "answer": answer.to_json(),
+            "duration": (timing.parse + timing.solve).as_secs_f64(),
+            "error": null,
+        }),
+        Err(error) => json!({
+            "day": day,
+            "part": part,
+            "answer": null,
+            "duration": null,
+            "error": error,
+        }),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Status {
+    Pass,
+    Fail(Answer),
+    Missing,
+    Error(String),
+}
+
+fn check(solution: &dyn Runnable, input: &Input, part: u8, answers: &Answers) -> Status {
+    let answer = match solution.run(input, part) {
+        Ok(answer) => answer,
+        Err(error) => return Status::Error(error.to_string()),
+    };
+
+    match answers.get(solution.day(), part) {
+        Some(expected) if expected.to_string() == answer.to_string() => Status::Pass,
+        Some(_) => Status::Fail(answer),
+        None => Status::Missing,
+    }
+}
+
+/// Multi-line answers are summarised so that they fit in a table row.
+fn summary(answer: &Answer) -> String {
+    let answer = answer.to_string();
+
+    match answer.lines().count() {
+        0 | 1 => answer,
+        lines => format!("<{} lines>", lines),
+    }
+}
+
+pub fn verify(source: &InputSource, day: Option<u8>, answers: &Answers) -> Result<()> {
+    let selected = select(source, day)?;
+    let mut failed = 0;
+    let mut total = 0;
+
+    println!("{:>3}  {:>4}  {:<7}  Details", "Day", "Part", "Result");
+
+    for solution in selected {
+        let input = source.read(solution.day());
+
+        for part in [1, 2] {
+            let status = match &input {
+                Ok(input) => check(solution, input, part, answers),
+                Err(error) => Status::Error(error.to_string()),
+            };
+
+            let (result, details) = match &status {
+                Status::Pass => ("pass", summary(answers.get(solution.day(), part).unwrap())),
+                Status::Fail(answer) => (
+                    "FAIL",
+                    format!(
+                        "got {}, expected {}",
+                        summary(answer),
+                        summary(answers.get(solution.day(), part).unwrap())
+                    ),
+                ),
+                Status::Missing => ("missing", "no recorded answer".to_string()),
+                Status::Error(message) => ("ERROR", message.clone()),
+            };
+
+            println!(
+                "{:>3}  {:>4}  {:<7}  {}",
+                solution.day(),
+                part,
+                result,
+                details
+            );
+
+            total += 1;
+            if matches!(status, Status::Fail(_) | Status::Error(_)) {
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(Error::Verification { failed, total });
+    }
+
+    Ok(())
+}
+
+pub fn bench(
+    source: &InputSource,
+    day: Option<u8>,
+    part: Option<u8>,
+    iterations: usize,
+    format: Format,
+) -> Result<()> {
+    let selected = select(source, day)?;
+    let parts = parts(part)?;
+    let mut measurements = vec![];
+
+    for solution in selected {
+        let input = source.read(solution.day())?;
+
+        for &part in parts.iter() {
+            measurements.push(bench::measure(solution, &input, part, iterations)?);
+        }
+    }
+
+    match format {
+        Format::Text => bench::print_table(&measurements),
+        Format::Json => {
+            let records: Vec<_> = measurements.iter().map(|m| m.to_json()).collect();
+            println!("{}", Value::Array(records));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn fetch(source: &InputSource, day: u8, server: &Server) -> Result<()> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::UnknownDay(day));
+    }
+
+    let dir = match source {
+        InputSource::Dir(dir) => dir,
+        _ => {
+            return Err(Error::Usage(
+                "inputs can only be fetched into an inputs directory".to_string(),
+            ))
+        }
+    };
+
+    if server::cache_input(server, dir, day)? {
+        println!("Downloaded {}", source.name(day));
+    } else {
+        println!("{} is already there", source.name(day));
+    }
+
+    Ok(())
+}
+
+pub fn submit(
+    source: &InputSource,
+    day: u8,
+    part: u8,
+    server: &Server,
+    history_path: &std::path::Path,
+) -> Result<()> {
+    let solution = registry::find(day).ok_or(Error::UnknownDay(day))?;
+    let answer = solution.run(&source.read(day)?, part)?;
+
+    // Answers drawn as text, like the CRT of day 10, have to be read by eye.
+    if let Answer::Text(text) = &answer {
+        if text.contains('\n') {
+            return Err(Error::Submission(format!(
+                "day {} part {} is a multi-line answer and has to be read and submitted by hand",
+                day, part
+            )));
+        }
+    }
+
+    let mut history = History::read(history_path)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+
+    for warning in history.check(day, part, &answer, now)? {
+        eprintln!("warning: {}", warning);
+    }
+
+    let (outcome, wait) = server.submit(day, part, &answer)?;
+
+    history.0.push(Attempt {
+        day,
+        part,
+        answer: answer.clone(),
+        outcome,
+        time: now,
+        wait,
+    });
+    history.write(history_path)?;
+
+    println!("Day {} part {}: {} is {}", day, part, answer, outcome);
+    if wait > 0 {
+        println!("Wait {}s before submitting again", wait);
+    }
+
+    Ok(())
+}
+
+/// Pairs every symbol of a reading with what it stands for.
+fn describe(reading: &str, meanings: &[&str]) -> String {
+    reading
+        .chars()
+        .zip(meanings)
+        .map(|(symbol, meaning)| format!("{}={}", symbol, meaning))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn guide(
+    source: &InputSource,
+    mapping: Mapping,
+    search: Option<Search>,
+    stats: Option<Format>,
+) -> Result<()> {
+    let day = Day2 {
+        rules: Rules::default(),
+        mapping,
+    };
+    day.rules.check()?;
+    day.mapping.check(&day.rules)?;
+
+    let input = source.read(2)?;
+    let rounds = day.parse(&input)?;
+
+    if let Some(format) = stats {
+        let reports = [1, 2]
+            .into_iter()
+            .map(|part| Ok(Report::new(&day.rules, part, &day.games(&rounds, part)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        match format {
+            Format::Text => day2::print_reports(&day.rules, &reports),
+            Format::Json => {
+                let records: Vec<_> = reports.iter().map(|r| r.to_json(&day.rules)).collect();
+                println!("{}", Value::Array(records));
+            }
+        }
+
+        return Ok(());
+    }
+
+    let Some(search) = search else {
+        println!("Part 1: {}", day.part_1(&rounds)?);
+        println!("Part 2: {}", day.part_2(&rounds)?);
+        return Ok(());
+    };
+
+    let shapes: Vec<_> = day.rules.shapes.iter().map(|(name, _)| *name).collect();
+
+    for (part, meanings) in [(1, &shapes[..]), (2, &["lose", "draw", "win"][..])] {
+        let readings = day.readings(&rounds, part)?;
+        let (reading, score) = match search {
+            Search::Max => readings.iter().max_by_key(|(_, score)| *score),
+            Search::Min => readings.iter().min_by_key(|(_, score)| *score),
+        }
+        .unwrap();
+
+        println!(
+            "Part {}: {} reading {}",
+            part,
+            score,
+            describe(reading, meanings)
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_describe() {
+    assert_eq!(
+        describe("ZXY", &["rock", "paper", "scissors"]),
+        "Z=rock X=paper Y=scissors"
+    );
+}
+
+#[test]
+fn test_parse_run() {
+    let cli = Cli::try_parse_from(["aoc2022", "run", "12", "--part", "2", "--input", "-"]).unwrap();
+    assert_eq!(cli.input_source(), InputSource::Stdin);
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            all,
+            format,
+            time,
+            parallel,
+            jobs,
+        } => {
+            assert_eq!(day, Some(12));
+            assert_eq!(part, Some(2));
+            assert!(!all);
+            assert_eq!(format, Format::Text);
+            assert!(!time);
+            assert!(!parallel);
+            assert_eq!(jobs, None);
+        }
+        command => panic!("unexpected command {:?}", command),
+    }
+
+    assert!(Cli::try_parse_from(["aoc2022", "run"]).is_err());
+    assert!(Cli::try_parse_from(["aoc2022", "run", "7", "--all"]).is_err());
+    assert!(Cli::try_parse_from(["aoc2022", "run", "--all", "--jobs", "2"]).is_err());
+}
+
+#[test]
+fn test_run_unknown() {
+    let source = InputSource::default();
+
+    assert!(matches!(
+        run(&source, Some(0), None, Format::Text, false, None),
+        Err(Error::UnknownDay(0))
+    ));
+    assert!(matches!(
+        run(&source, Some(26), None, Format::Text, false, None),
+        Err(Error::UnknownDay(26))
+    ));
+    assert!(matches!(
+        run(&source, Some(1), Some(3), Format::Text, false, None),
+        Err(Error::UnknownPart(3))
+    ));
+}
+
+#[test]
+fn test_run_input_errors() {
+    let error = run(
+        &InputSource::File("missing.txt".into()),
+        None,
+        None,
+        Format::Text,
+        false,
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "--input can only be used when running a single day"
+    );
+
+    let error = run(
+        &InputSource::File("missing.txt".into()),
+        Some(1),
+        None,
+        Format::Text,
+        false,
+        None,
+    )
+    .unwrap_err();
+    assert!(error.to_string().starts_with("missing.txt: "));
+}
+
+#[test]
+fn test_parse_verify() {
+    let cli = Cli::try_parse_from(["aoc2022", "verify"]).unwrap();
+
+    match cli.command {
+        Command::Verify { day, answers } => {
+            assert_eq!(day, None);
+            assert_eq!(answers, PathBuf::from(ANSWERS_FILE));
+        }
+        command => panic!("unexpected command {:?}", command),
+    }
+}
+
+#[test]
+fn test_check() {
+    let answers = Answers::parse(r#"{ "1": { "1": 24000, "2": 1 } }"#).unwrap();
+    let day1 = registry::find(1).unwrap();
+    let input = crate::file::read_example(1);
+
+    assert_eq!(check(day1, &input, 1, &answers), Status::Pass);
+    assert_eq!(
+        check(day1, &input, 2, &answers),
+        Status::Fail(Answer::Number(45000))
+    );
+    assert_eq!(
+        check(registry::find(2).unwrap(), &"A Y".into(), 1, &answers),
+        Status::Missing
+    );
+    assert_eq!(
+        check(registry::find(2).unwrap(), &"A Q".into(), 1, &answers),
+        Status::Error("day2.txt:1: unknown symbol \"Q\"".to_string())
+    );
+}
+
+#[test]
+fn test_recorded_answers_are_readable() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(ANSWERS_FILE);
+    let answers = Answers::read(&path).unwrap();
+
+    assert_eq!(answers.get(1, 1), Some(&Answer::Number(67016)));
+}
+
+#[test]
+fn test_parse_bench() {
+    let cli =
+        Cli::try_parse_from(["aoc2022", "bench", "8", "-n", "3", "--format", "json"]).unwrap();
+
+    match cli.command {
+        Command::Bench {
+            day,
+            part,
+            iterations,
+            format,
+        } => {
+            assert_eq!(day, Some(8));
+            assert_eq!(part, None);
+            assert_eq!(iterations, 3);
+            assert_eq!(format, Format::Json);
+        }
+        command => panic!("unexpected command {:?}", command),
+    }
+}
+
+#[test]
+fn test_record() {
+    let timing = Timing {
+        parse: std::time::Duration::from_millis(250),
+        solve: std::time::Duration::from_millis(750),
+    };
+
+    assert_eq!(
+        record(5, 1, &Ok((Answer::Text("CMZ".to_string()), timing))),
+        json!({ "day": 5, "part": 1, "answer": "CMZ", "duration": 1.0, "error": null })
+    );
+    assert_eq!(
+        record(3, 2, &Err("unknown part 3".to_string())),
+        json!({ "day": 3, "part": 2, "answer": null, "duration": null, "error": "unknown part 3" })
+    );
+}
+
+#[test]
+fn test_jobs() {
+    assert_eq!(jobs(false, None), None);
+    assert_eq!(jobs(true, Some(3)), Some(3));
+    assert!(jobs(true, None).unwrap() >= 1);
+}
+
+#[test]
+fn test_run_parallel() {
+    let source =
+        InputSource::Dir(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures"));
+
+    run(&source, None, Some(1), Format::Json, false, Some(4)).unwrap();
+
+    let error = run(
+        &InputSource::Dir("missing".into()),
+        Some(2),
+        None,
+        Format::Text,
+        false,
+        Some(4),
+    )
+    .unwrap_err();
+    assert!(error.to_string().starts_with("missing/day2.txt: "));
+}
+
+#[test]
+fn test_parse_new_day() {
+    let cli = Cli::try_parse_from(["aoc2022", "new-day", "16"]).unwrap();
+
+    match cli.command {
+        Command::NewDay { day, root } => {
+            assert_eq!(day, 16);
+            assert_eq!(root, PathBuf::from("."));
+        }
+        command => panic!("unexpected command {:?}", command),
+    }
+}
+
+#[test]
+fn test_parse_fetch() {
+    let cli = Cli::try_parse_from([
+        "aoc2022",
+        "fetch",
+        "3",
+        "--session",
+        "secret",
+        "--base-url",
+        "http://localhost:8080/2022",
+    ])
+    .unwrap();
+
+    match cli.command {
+        Command::Fetch { day, server } => {
+            assert_eq!(day, 3);
+            assert_eq!(server.session, "secret");
+            assert_eq!(server.base_url, "http://localhost:8080/2022");
+        }
+        command => panic!("unexpected command {:?}", command),
+    }
+}
+
+#[test]
+fn test_fetch_needs_a_directory() {
+    let server = Server::new("http://localhost:1", "secret");
+
+    assert!(matches!(
+        fetch(&InputSource::Stdin, 3, &server),
+        Err(Error::Usage(_))
+    ));
+    assert!(matches!(
+        fetch(&InputSource::Stdin, 26, &server),
+        Err(Error::UnknownDay(26))
+    ));
+}
+
+#[test]
+fn test_submit() {
+    let source =
+        InputSource::Dir(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures"));
+    let history =
+        std::env::temp_dir().join(format!("aoc2022-submissions-{}.json", std::process::id()));
+
+    // A single response: the second submission must not reach the server.
+    let (base_url, requests) = crate::mock_server::serve(vec![(
+        200,
+        "<p>That's not the right answer; your answer is too high.</p>",
+    )]);
+    let server = Server::new(&base_url, "secret");
+
+    submit(&source, 1, 1, &server, &history).unwrap();
+    assert_eq!(
+        submit(&source, 1, 1, &server, &history)
+            .unwrap_err()
+            .to_string(),
+        "24000 was already submitted and was too high"
+    );
+
+    assert_eq!(requests.join().unwrap()[0].body, "level=1&answer=24000");
+    let attempts = History::read(&history).unwrap().0;
+    assert_eq!(attempts.len(), 1);
+    assert_eq!(attempts[0].answer, Answer::Number(24000));
+
+    std::fs::remove_file(history).unwrap();
+}
+
+#[test]
+fn test_parse_watch() {
+    let cli = Cli::try_parse_from(["aoc2022", "watch", "9", "--interval", "100"]).unwrap();
+
+    match cli.command {
+        Command::Watch {
+            day,
+            root,
+            interval,
+        } => {
+            assert_eq!(day, 9);
+            assert_eq!(root, PathBuf::from("."));
+            assert_eq!(interval, 100);
+        }
+        command => panic!("unexpected command {:?}", command),
+    }
+}
+
+#[test]
+fn test_parse_guide() {
+    let cli =
+        Cli::try_parse_from(["aoc2022", "guide", "--hands", "ZXY", "--search", "max"]).unwrap();
+
+    match cli.command {
+        Command::Guide {
+            opponent,
+            hands,
+            outcomes,
+            search,
+            stats,
+            format,
+        } => {
+            assert_eq!(opponent, "ABC");
+            assert_eq!(hands, "ZXY");
+            assert_eq!(outcomes, "XYZ");
+            assert_eq!(search, Some(Search::Max));
+            assert!(!stats);
+            assert_eq!(format, Format::Text);
+        }
+        command => panic!("unexpected command {:?}", command),
+    }
+}
+
+#[test]
+fn test_parse_guide_stats() {
+    let cli = Cli::try_parse_from(["aoc2022", "guide", "--stats", "--format", "json"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::Guide {
+            stats: true,
+            format: Format::Json,
+            ..
+        }
+    ));
+
+    assert!(Cli::try_parse_from(["aoc2022", "guide", "--format", "json"]).is_err());
+    assert!(Cli::try_parse_from(["aoc2022", "guide", "--stats", "--search", "max"]).is_err());
+}
+
+#[test]
+fn test_run_json_failures() {
+    let source = InputSource::Dir("/nonexistent".into());
+
+    assert_eq!(
+        run(&source, Some(2), None, Format::Json, false, None)
+            .unwrap_err()
+            .to_string(),
+        "2 of 2 parts failed"
+    );
+    assert!(run(&source, Some(2), None, Format::Json, false, Some(2)).is_err());
+}
+
+#[test]
+fn test_submit_multi_line_answer() {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+    let server = Server::new("http://127.0.0.1:9", "session");
+
+    assert_eq!(
+        submit(
+            &InputSource::Dir(fixtures.clone()),
+            10,
+            2,
+            &server,
+            &fixtures.join("missing-history.json"),
+        )
+        .unwrap_err()
+        .to_string(),
+        "day 10 part 2 is a multi-line answer and has to be read and submitted by hand"
+    );
+}
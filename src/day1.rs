@@ -1,26 +1,124 @@
+use itertools::process_results;
+
 use crate::{
-    file::read_lines, lines_as_numbers::StringToNumbersTrait, summed_groups::SummedGroupsTrait,
+    error::{Error, Result},
+    input::Input,
+    lines_as_numbers::StringToNumbersTrait,
+    solution::Solution,
+    summed_groups::SummedGroupsTrait,
+    top_k::TopKTrait,
 };
 
-pub fn part_1() {
-    let elves_max = read_lines("day1.txt")
-        .as_numbers()
-        .summed_groups()
-        .max()
-        .unwrap();
+/// The calories carried by each of the `n` elves carrying the most, largest
+/// first, in a single pass over the input that keeps no more than `n` elves in
+/// memory.
+pub fn top_elves(input: &Input, n: usize) -> Result<Vec<i32>> {
+    process_results(input.lines().as_numbers(1), |calories| {
+        calories.summed_groups().top_k(n)
+    })
+}
+
+/// How many elves part 2 adds up.
+const TOP_ELVES: usize = 3;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    /// The calories of the elves carrying the most, largest first. No other
+    /// elf matters to either part.
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        top_elves(input, TOP_ELVES)
+    }
+
+    fn part_1(&self, top_elves: &Self::Input) -> Result<Self::Answer1> {
+        top_elves
+            .first()
+            .copied()
+            .ok_or_else(|| Error::no_answer(1, "no elves"))
+    }
+
+    fn part_2(&self, top_elves: &Self::Input) -> Result<Self::Answer2> {
+        Ok(top_elves.iter().sum())
+    }
+}
 
-    println!("Heaviest elf: {:?}", elves_max);
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_1() {
+    assert_eq!(
+        Day1.part_1(&Day1.parse(&crate::file::read_input(1)).unwrap())
+            .unwrap(),
+        67016
+    );
 }
 
-pub fn part_2() {
-    let mut summed_elves: Vec<i32> = read_lines("day1.txt")
-        .as_numbers()
-        .summed_groups()
-        .collect();
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_2() {
+    assert_eq!(
+        Day1.part_2(&Day1.parse(&crate::file::read_input(1)).unwrap())
+            .unwrap(),
+        200116
+    );
+}
 
-    summed_elves.sort();
+#[test]
+fn test_example_part_1() {
+    assert_eq!(
+        Day1.part_1(&Day1.parse(&crate::file::read_example(1)).unwrap())
+            .unwrap(),
+        24000
+    );
+}
+
+#[test]
+fn test_example_part_2() {
+    assert_eq!(
+        Day1.part_2(&Day1.parse(&crate::file::read_example(1)).unwrap())
+            .unwrap(),
+        45000
+    );
+}
 
-    let three_top = summed_elves.iter().rev().take(3).sum::<i32>();
+#[test]
+fn test_parse() {
+    assert_eq!(
+        Day1.parse(&"100\n0\n\n0\n\n200".into()).unwrap(),
+        vec![200, 100, 0]
+    );
+    assert_eq!(
+        Day1.parse(&"100\n\nabc".into()).unwrap_err().to_string(),
+        "day1.txt:3: invalid number \"abc\""
+    );
+}
+
+#[test]
+fn test_top_elves() {
+    let input = crate::file::read_example(1);
+
+    assert_eq!(top_elves(&input, 1).unwrap(), vec![24000]);
+    assert_eq!(top_elves(&input, 3).unwrap(), vec![24000, 11000, 10000]);
+    assert_eq!(top_elves(&input, 10).unwrap().len(), 5);
+    assert_eq!(
+        top_elves(&"1\n\nx".into(), 3).unwrap_err().to_string(),
+        "day1.txt:3: invalid number \"x\""
+    );
+}
 
-    println!("Elves: {:?}", three_top);
+#[test]
+fn test_no_elves() {
+    assert_eq!(
+        Day1.part_1(&Day1.parse(&"".into()).unwrap())
+            .unwrap_err()
+            .to_string(),
+        "day1.txt: no elves"
+    );
 }
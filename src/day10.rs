@@ -1,24 +1,34 @@
 use std::fmt::Debug;
 
-use crate::file::read_lines;
+use crate::{
+    error::{parse_lines, parse_number, Error, Result},
+    input::Input,
+    solution::Solution,
+};
+
+const DAY: u8 = 10;
 
 #[derive(Debug, Copy, Clone)]
-struct CPU {
+struct Cpu {
     x: i64,
 }
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     AddX(i64),
     Noop,
 }
 
 impl Instruction {
-    fn new(instruction: Vec<String>) -> Self {
-        match instruction[0].as_str() {
-            "addx" => Instruction::AddX(instruction[1].parse().unwrap()),
-            "noop" => Instruction::Noop,
-            _ => panic!("unknown instruction {:?}", instruction[0]),
+    fn new(instruction: Vec<&str>) -> Result<Self> {
+        match instruction.as_slice() {
+            ["addx", value] => Ok(Instruction::AddX(parse_number(DAY, value)?)),
+            ["noop"] => Ok(Instruction::Noop),
+            _ => Err(Error::parse(
+                DAY,
+                "unknown instruction",
+                &instruction.join(" "),
+            )),
         }
     }
 
@@ -37,7 +47,7 @@ enum Effect {
 }
 
 impl Effect {
-    fn apply(&self, cpu: &mut CPU) {
+    fn apply(&self, cpu: &mut Cpu) {
         match self {
             Effect::AddX(v) => cpu.x += v,
             Effect::Noop => {}
@@ -45,25 +55,12 @@ impl Effect {
     }
 }
 
-fn instructions() -> Vec<Instruction> {
-    read_lines("day10.txt")
-        .map(|line| {
-            line.unwrap()
-                .split_whitespace()
-                .map(|x| x.to_string())
-                .collect::<Vec<_>>()
-        })
-        .map(Instruction::new)
-        .collect()
-}
-
-fn cycles(instructions: Vec<Instruction>, mut cpu: CPU) -> Vec<(usize, CPU)> {
+fn cycles(instructions: &[Instruction], mut cpu: Cpu) -> Vec<(usize, Cpu)> {
     let mut last_effect: Option<Effect> = None;
 
     instructions
         .iter()
-        .map(|instr| instr.to_cycles())
-        .flatten()
+        .flat_map(|instr| instr.to_cycles())
         .map(|effect| {
             if let Some(effect) = &last_effect {
                 effect.apply(&mut cpu);
@@ -76,46 +73,141 @@ fn cycles(instructions: Vec<Instruction>, mut cpu: CPU) -> Vec<(usize, CPU)> {
         .collect::<Vec<_>>()
 }
 
-pub fn part_1() {
-    let cpu = CPU { x: 1 };
-    let instructions = instructions();
-    let cycles = cycles(instructions, cpu);
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+    type Answer1 = i64;
+    type Answer2 = String;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        parse_lines(input.as_str(), |line| {
+            Instruction::new(line.split_whitespace().collect::<Vec<_>>())
+        })
+    }
+
+    fn part_1(&self, instructions: &Self::Input) -> Result<Self::Answer1> {
+        let cpu = Cpu { x: 1 };
+        let cycles = cycles(instructions, cpu);
+
+        let signal_strength = (20..=220)
+            .step_by(40)
+            .map(|cycle| {
+                let (_, cpu) = cycles.get(cycle - 1).ok_or_else(|| {
+                    Error::no_answer(DAY, &format!("the program ends before cycle {}", cycle))
+                })?;
 
-    let signal_strength: Vec<_> = (20..=220)
-        .step_by(40)
-        .map(|cycle| cycles[cycle - 1].1.x * cycle as i64)
-        .collect();
+                Ok(cpu.x * cycle as i64)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(signal_strength.iter().sum::<i64>())
+    }
 
-    println!("{:?}", signal_strength.iter().sum::<i64>());
+    fn part_2(&self, instructions: &Self::Input) -> Result<Self::Answer2> {
+        let cpu = Cpu { x: 1 };
+        let cycles = cycles(instructions, cpu);
+
+        let mut crt = String::new();
+
+        for (cycle, cpu) in cycles {
+            let sprite = (cpu.x - 1)..=(cpu.x + 1);
+            let crt_pos = cycle as i64 % 40;
+
+            if sprite.contains(&crt_pos) {
+                crt.push('#')
+            } else {
+                crt.push('.')
+            }
+
+            if cycle % 40 == 39 {
+                crt.push('\n');
+            }
+        }
+
+        Ok(crt)
+    }
 }
 
 #[test]
+#[ignore = "reads the full puzzle input"]
 fn test_part_1() {
-    part_1()
+    assert_eq!(
+        Day10
+            .part_1(&Day10.parse(&crate::file::read_input(10)).unwrap())
+            .unwrap(),
+        14860
+    );
 }
 
-pub fn part_2() {
-    let cpu = CPU { x: 1 };
-    let instructions = instructions();
-    let cycles = cycles(instructions, cpu);
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_2() {
+    assert_eq!(
+        Day10
+            .part_2(&Day10.parse(&crate::file::read_input(10)).unwrap())
+            .unwrap(),
+        concat!(
+            "###...##..####.####.#..#.#..#.###..#..#.\n",
+            "#..#.#..#....#.#....#..#.#..#.#..#.#.#..\n",
+            "#..#.#......#..###..####.#..#.#..#.##...\n",
+            "###..#.##..#...#....#..#.#..#.###..#.#..\n",
+            "#.#..#..#.#....#....#..#.#..#.#.#..#.#..\n",
+            "#..#..###.####.####.#..#..##..#..#.#..#.\n",
+        )
+    );
+}
 
-    for (cycle, cpu) in cycles {
-        let sprite = (cpu.x - 1)..=(cpu.x + 1);
-        let crt_pos = cycle as i64 % 40;
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Day10.parse(&"noop\naddx x".into()).unwrap_err().to_string(),
+        "day10.txt:2: invalid number \"x\""
+    );
+    assert_eq!(
+        Day10.parse(&"subx 3".into()).unwrap_err().to_string(),
+        "day10.txt:1: unknown instruction \"subx 3\""
+    );
+}
 
-        if sprite.contains(&crt_pos) {
-            print!("#")
-        } else {
-            print!(".")
-        }
+#[test]
+fn test_example_part_1() {
+    assert_eq!(
+        Day10
+            .part_1(&Day10.parse(&crate::file::read_example(10)).unwrap())
+            .unwrap(),
+        13140
+    );
+}
 
-        if cycle % 40 == 39 {
-            println!("");
-        }
-    }
+#[test]
+fn test_example_part_2() {
+    assert_eq!(
+        Day10
+            .part_2(&Day10.parse(&crate::file::read_example(10)).unwrap())
+            .unwrap(),
+        concat!(
+            "##..##..##..##..##..##..##..##..##..##..\n",
+            "###...###...###...###...###...###...###.\n",
+            "####....####....####....####....####....\n",
+            "#####.....#####.....#####.....#####.....\n",
+            "######......######......######......####\n",
+            "#######.......#######.......#######.....\n",
+        )
+    );
 }
 
 #[test]
-fn test_part_2() {
-    part_2()
+fn test_short_program() {
+    assert_eq!(
+        Day10
+            .part_1(&Day10.parse(&"noop".into()).unwrap())
+            .unwrap_err()
+            .to_string(),
+        "day10.txt: the program ends before cycle 20"
+    );
 }
@@ -1,33 +1,43 @@
 use std::fmt::Debug;
 
-use itertools::Itertools;
 use regex::{Match, Regex};
 
-use crate::file::read_lines;
+use crate::{
+    error::{parse_number, Error, Result},
+    input::Input,
+    solution::Solution,
+    top_k::TopKTrait,
+};
+
+const DAY: u8 = 11;
 
 #[derive(Debug, Clone)]
 enum Operation {
     MultiplyOld,
+    AddOld,
     Multiply(i64),
     Add(i64),
 }
 
-impl From<(Option<Match<'_>>, Option<Match<'_>>)> for Operation {
-    fn from((operation, value): (Option<Match<'_>>, Option<Match<'_>>)) -> Self {
-        if value.unwrap().as_str() == "old" {
-            return Operation::MultiplyOld;
-        }
+impl TryFrom<(Option<Match<'_>>, Option<Match<'_>>)> for Operation {
+    type Error = Error;
+
+    fn try_from((operation, value): (Option<Match<'_>>, Option<Match<'_>>)) -> Result<Self> {
+        let operation = operation.unwrap().as_str();
+        let value = value.unwrap().as_str();
 
-        match operation.unwrap().as_str() {
-            "*" => Operation::Multiply(value.unwrap().as_str().parse().unwrap()),
-            "+" => Operation::Add(value.unwrap().as_str().parse().unwrap()),
-            _ => panic!("unsupported operation"),
+        match (operation, value) {
+            ("*", "old") => Ok(Operation::MultiplyOld),
+            ("+", "old") => Ok(Operation::AddOld),
+            ("*", _) => Ok(Operation::Multiply(parse_number(DAY, value)?)),
+            ("+", _) => Ok(Operation::Add(parse_number(DAY, value)?)),
+            _ => Err(Error::parse(DAY, "unsupported operation", operation)),
         }
     }
 }
 
 #[derive(Clone)]
-struct Monkey {
+pub struct Monkey {
     items: Vec<i64>,
     operation: Operation,
     test: (i64, usize, usize),
@@ -44,6 +54,7 @@ impl Inspect for Monkey {
 
         match self.operation {
             Operation::MultiplyOld => item *= item,
+            Operation::AddOld => item += item,
             Operation::Multiply(x) => item *= x,
             Operation::Add(x) => item += x,
         }
@@ -85,35 +96,56 @@ where
     }
 }
 
-fn monkeys() -> Vec<Monkey> {
+fn monkeys(input: &Input) -> Result<Vec<Monkey>> {
     let regex = Regex::new(r"Monkey \d+:\n  Starting items: (.*)\n  Operation: new = old ([+*]) (.+)\n  Test: divisible by (\d+)\n    If true: throw to monkey (\d+)\n    If false: throw to monkey (\d+)").unwrap();
 
-    read_lines("day11.txt")
-        .map(|line| line.unwrap())
-        .chunks(7)
-        .into_iter()
-        .map(|mut chunk| {
-            let definition = chunk.join("\n");
-
-            let captures = regex.captures(definition.as_str()).unwrap();
-
-            Monkey {
-                items: captures
-                    .get(1)
-                    .unwrap()
-                    .as_str()
-                    .split(", ")
-                    .map(|i| i.parse().unwrap())
-                    .collect_vec(),
-                operation: (captures.get(2), captures.get(3)).into(),
-                test: (
-                    captures.get(4).unwrap().as_str().parse().unwrap(),
-                    captures.get(5).unwrap().as_str().parse().unwrap(),
-                    captures.get(6).unwrap().as_str().parse().unwrap(),
-                ),
-            }
+    let monkeys = input
+        .paragraphs()
+        .map(|paragraph| {
+            let number = paragraph[0].1;
+            let definition = paragraph
+                .iter()
+                .map(|(line, _)| *line)
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let monkey = || -> Result<Monkey> {
+                let captures = regex
+                    .captures(definition.as_str())
+                    .ok_or_else(|| Error::parse(DAY, "invalid monkey", &definition))?;
+                let number = |index| parse_number(DAY, captures.get(index).unwrap().as_str());
+
+                let divisor = number(4)?;
+                if divisor == 0 {
+                    return Err(Error::parse(DAY, "divisible by zero", "0"));
+                }
+
+                Ok(Monkey {
+                    items: captures
+                        .get(1)
+                        .unwrap()
+                        .as_str()
+                        .split(", ")
+                        .map(|i| parse_number(DAY, i))
+                        .collect::<Result<Vec<_>>>()?,
+                    operation: (captures.get(2), captures.get(3)).try_into()?,
+                    test: (divisor, number(5)? as usize, number(6)? as usize),
+                })
+            };
+
+            monkey().map_err(|error| error.at_line(number))
         })
-        .collect()
+        .collect::<Result<Vec<Monkey>>>()?;
+
+    for monkey in monkeys.iter() {
+        for target in [monkey.test.1, monkey.test.2] {
+            if target >= monkeys.len() {
+                return Err(Error::parse(DAY, "unknown monkey", &target.to_string()));
+            }
+        }
+    }
+
+    Ok(monkeys)
 }
 
 fn round<T>(monkeys: &mut Vec<T>, factors: i64) -> Vec<usize>
@@ -136,29 +168,6 @@ where
     inspect
 }
 
-pub fn part_1() {
-    let mut monkeys = monkeys();
-
-    let inspections: _ = (0..20)
-        .map(|_| round(&mut monkeys, 1))
-        .reduce(|acc, value| {
-            acc.iter()
-                .enumerate()
-                .map(|(index, val)| value[index] + val)
-                .collect()
-        })
-        .unwrap();
-
-    let product: usize = inspections.iter().sorted().rev().take(2).product();
-
-    println!("{:?}", product);
-}
-
-#[test]
-fn test_part_1() {
-    part_1()
-}
-
 #[derive(Clone)]
 struct MonkeyWithoutRelief {
     items: Vec<i64>,
@@ -180,6 +189,7 @@ impl Inspect for MonkeyWithoutRelief {
 
         match self.operation {
             Operation::MultiplyOld => item *= item,
+            Operation::AddOld => item += item,
             Operation::Multiply(x) => item *= x,
             Operation::Add(x) => item += x,
         }
@@ -198,34 +208,153 @@ impl Inspect for MonkeyWithoutRelief {
     }
 }
 
-pub fn part_2() {
-    let mut monkeys: Vec<MonkeyWithoutRelief> = monkeys()
-        .iter()
-        .map(|monkey| MonkeyWithoutRelief {
-            items: monkey.items.clone(),
-            operation: monkey.operation.clone(),
-            test: monkey.test,
-        })
-        .collect();
+pub struct Day11;
 
-    let factors = monkeys.iter().map(|monkey| monkey.test.0).product::<i64>();
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let inspections: _ = (0..10000)
-        .map(|_| round(&mut monkeys, factors))
-        .reduce(|acc, value| {
-            acc.iter()
-                .enumerate()
-                .map(|(index, val)| value[index] + val)
-                .collect()
-        })
-        .unwrap();
+    fn day(&self) -> u8 {
+        DAY
+    }
 
-    let product: usize = inspections.iter().sorted().rev().take(2).product();
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        monkeys(input)
+    }
 
-    println!("{:?}", product);
+    fn part_1(&self, monkeys: &Self::Input) -> Result<Self::Answer1> {
+        let mut monkeys = monkeys.clone();
+
+        let inspections = (0..20)
+            .map(|_| round(&mut monkeys, 1))
+            .reduce(|acc, value| {
+                acc.iter()
+                    .enumerate()
+                    .map(|(index, val)| value[index] + val)
+                    .collect()
+            })
+            .unwrap();
+
+        Ok(inspections.iter().top_k(2).into_iter().product())
+    }
+
+    fn part_2(&self, monkeys: &Self::Input) -> Result<Self::Answer2> {
+        let mut monkeys: Vec<MonkeyWithoutRelief> = monkeys
+            .iter()
+            .map(|monkey| MonkeyWithoutRelief {
+                items: monkey.items.clone(),
+                operation: monkey.operation.clone(),
+                test: monkey.test,
+            })
+            .collect();
+
+        let factors = monkeys.iter().map(|monkey| monkey.test.0).product::<i64>();
+
+        let inspections = (0..10000)
+            .map(|_| round(&mut monkeys, factors))
+            .reduce(|acc, value| {
+                acc.iter()
+                    .enumerate()
+                    .map(|(index, val)| value[index] + val)
+                    .collect()
+            })
+            .unwrap();
+
+        Ok(inspections.iter().top_k(2).into_iter().product())
+    }
 }
 
 #[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_1() {
+    assert_eq!(
+        Day11
+            .part_1(&Day11.parse(&crate::file::read_input(11)).unwrap())
+            .unwrap(),
+        56595
+    );
+}
+
+#[test]
+#[ignore = "reads the full puzzle input"]
 fn test_part_2() {
-    part_2()
+    assert_eq!(
+        Day11
+            .part_2(&Day11.parse(&crate::file::read_input(11)).unwrap())
+            .unwrap(),
+        15693274740
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    let monkey = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 0";
+
+    assert_eq!(
+        Day11.parse(&monkey.into()).unwrap_err().to_string(),
+        "day11.txt: unknown monkey \"2\""
+    );
+    assert_eq!(
+        Day11
+            .parse(&monkey.replace("* 19", "* x").into())
+            .unwrap_err()
+            .to_string(),
+        "day11.txt:1: invalid number \"x\""
+    );
+    assert_eq!(
+        Day11
+            .parse(&monkey.replace("by 23", "by 0").into())
+            .unwrap_err()
+            .to_string(),
+        "day11.txt:1: divisible by zero \"0\""
+    );
+}
+
+#[test]
+fn test_operations() {
+    let monkey = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * old
+  Test: divisible by 23
+    If true: throw to monkey 0
+    If false: throw to monkey 0";
+
+    for (operation, worry) in [
+        ("* old", 79 * 79 / 3),
+        ("+ old", 79 * 2 / 3),
+        ("* 2", 79 * 2 / 3),
+        ("+ 2", 81 / 3),
+    ] {
+        let monkeys = Day11
+            .parse(&monkey.replace("* old", operation).into())
+            .unwrap();
+
+        assert_eq!(monkeys[0].operate(0, 1), (0, worry), "{}", operation);
+    }
+}
+
+#[test]
+fn test_example_part_1() {
+    assert_eq!(
+        Day11
+            .part_1(&Day11.parse(&crate::file::read_example(11)).unwrap())
+            .unwrap(),
+        10605
+    );
+}
+
+#[test]
+fn test_example_part_2() {
+    assert_eq!(
+        Day11
+            .part_2(&Day11.parse(&crate::file::read_example(11)).unwrap())
+            .unwrap(),
+        2713310158
+    );
 }
@@ -5,10 +5,16 @@ use std::{
 
 use itertools::Itertools;
 
-use crate::file::read_lines;
+use crate::{
+    error::{Error, Result},
+    input::Input,
+    solution::Solution,
+};
+
+const DAY: u8 = 12;
 
 #[derive(Debug)]
-struct Map {
+pub struct Map {
     map: Vec<char>,
     size: (usize, usize),
     start_pos: (i32, i32),
@@ -21,20 +27,42 @@ impl Display for Map {
             for x in 0..self.size.0 {
                 write!(f, "{}", self.get((x as i32, y as i32)).unwrap().1)?;
             }
-            writeln!(f, "")?;
+            writeln!(f)?;
         }
 
         Ok(())
     }
 }
 
-impl From<Vec<Vec<char>>> for Map {
-    fn from(map: Vec<Vec<char>>) -> Self {
-        let size = (map[0].len(), map.len());
+impl TryFrom<Vec<Vec<char>>> for Map {
+    type Error = Error;
+
+    fn try_from(map: Vec<Vec<char>>) -> Result<Self> {
+        let size = (map.first().map_or(0, |row| row.len()), map.len());
+
+        for (row, number) in map.iter().zip(1..) {
+            let text = || row.iter().collect::<String>();
+
+            if row.len() != size.0 {
+                return Err(Error::parse(DAY, "uneven row", &text()).at_line(number));
+            }
 
-        let map = map.iter().flatten().map(|x| *x).collect::<Vec<char>>();
-        let start_pos = map.iter().find_position(|x| **x == 'S').unwrap().0;
-        let end_pos = map.iter().find_position(|x| **x == 'E').unwrap().0;
+            if let Some(c) = row.iter().find(|c| !matches!(c, 'a'..='z' | 'S' | 'E')) {
+                return Err(Error::parse(DAY, "invalid elevation", &c.to_string()).at_line(number));
+            }
+        }
+
+        let map = map.iter().flatten().copied().collect::<Vec<char>>();
+        let start_pos = map
+            .iter()
+            .find_position(|x| **x == 'S')
+            .ok_or_else(|| Error::parse(DAY, "missing start position", "S"))?
+            .0;
+        let end_pos = map
+            .iter()
+            .find_position(|x| **x == 'E')
+            .ok_or_else(|| Error::parse(DAY, "missing best signal position", "E"))?
+            .0;
 
         let map = map
             .iter()
@@ -45,18 +73,18 @@ impl From<Vec<Vec<char>>> for Map {
             })
             .collect();
 
-        Map {
+        Ok(Map {
             map,
             size,
             start_pos: ((start_pos % size.0) as i32, (start_pos / size.0) as i32),
             end_pos: ((end_pos % size.0) as i32, (end_pos / size.0) as i32),
-        }
+        })
     }
 }
 
 impl Map {
     fn get(&self, position: (i32, i32)) -> Option<((i32, i32), char)> {
-        let index = (position.1 as i32 * self.size.0 as i32) + position.0 as i32;
+        let index = (position.1 * self.size.0 as i32) + position.0;
         let x = position.0;
         let y = position.1;
 
@@ -79,7 +107,7 @@ impl Map {
     fn possible_adjacent(&self, position: (i32, i32)) -> Vec<((i32, i32), char)> {
         let current = self.get(position).unwrap();
 
-        vec![
+        [
             self.get((position.0 + 1, position.1)),
             self.get((position.0 - 1, position.1)),
             self.get((position.0, position.1 + 1)),
@@ -102,9 +130,9 @@ impl Map {
                     print!("{}", self.get((x as i32, y as i32)).unwrap().1);
                 }
             }
-            println!("");
+            println!();
         }
-        println!("");
+        println!();
     }
 }
 
@@ -116,22 +144,25 @@ fn test_map() {
         vec!['e', 'o', 'n', 'o', 'o', 'o', 'n'],
         vec!['E', 'o', 'o', 'o', 'o', 'o', 'n'],
     ]
-    .into();
-    println!("{:?}", map.possible_adjacent((1, 0)));
-    println!("{:?}", map.possible_adjacent((1, 1)));
-    println!("{:?}", map.possible_adjacent((1, 2)));
-    println!("{:?}", map.possible_adjacent((1, 3)));
-    println!("{:?}", map.possible_adjacent((4, 1)));
-    println!("{:?}", map.possible_adjacent((5, 1)));
-    println!("{:?}", map.find_all('o'));
-}
+    .try_into()
+    .unwrap();
+
+    assert_eq!(
+        map.possible_adjacent((1, 0)),
+        vec![((2, 0), 'n'), ((0, 0), 'a'), ((1, 1), 'o')]
+    );
+    assert_eq!(map.possible_adjacent((0, 0)), vec![((0, 1), 'b')]);
+    assert_eq!(map.possible_adjacent((0, 2)), vec![((0, 1), 'b')]);
+    assert_eq!(
+        map.possible_adjacent((0, 3)),
+        vec![((1, 3), 'o'), ((0, 2), 'e')]
+    );
 
-fn map() -> Map {
-    read_lines("day12.txt")
-        .map(|line| line.unwrap())
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>()
-        .into()
+    assert_eq!(map.find_all('a'), vec![(0, 0)]);
+    assert_eq!(
+        map.find_all('n'),
+        vec![(2, 0), (6, 0), (2, 1), (6, 1), (2, 2), (6, 2), (6, 3)]
+    );
 }
 
 fn bfs(map: &Map, start_position: (i32, i32)) -> Option<Vec<((i32, i32), char)>> {
@@ -160,34 +191,112 @@ fn bfs(map: &Map, start_position: (i32, i32)) -> Option<Vec<((i32, i32), char)>>
     None
 }
 
-pub fn part_1() {
-    let map = map();
+fn no_path() -> Error {
+    Error::no_answer(DAY, "no path to E")
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Map;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(|line| line.chars().collect::<Vec<char>>())
+            .collect::<Vec<Vec<char>>>()
+            .try_into()
+    }
 
-    let path = bfs(&map, map.start_pos).unwrap();
+    fn part_1(&self, map: &Self::Input) -> Result<Self::Answer1> {
+        let path = bfs(map, map.start_pos).ok_or_else(no_path)?;
+
+        Ok(path.len())
+    }
 
-    println!("it took {}", path.len());
+    fn part_2(&self, map: &Self::Input) -> Result<Self::Answer2> {
+        map.find_all('a')
+            .iter()
+            .filter_map(|position| bfs(map, *position))
+            .map(|path| path.len())
+            .min()
+            .ok_or_else(no_path)
+    }
 }
 
 #[test]
+#[ignore = "reads the full puzzle input"]
 fn test_part_1() {
-    part_1()
+    assert_eq!(
+        Day12
+            .part_1(&Day12.parse(&crate::file::read_input(12)).unwrap())
+            .unwrap(),
+        394
+    );
 }
 
-pub fn part_2() {
-    let map = map();
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_2() {
+    assert_eq!(
+        Day12
+            .part_2(&Day12.parse(&crate::file::read_input(12)).unwrap())
+            .unwrap(),
+        388
+    );
+}
 
-    let shortest_path = map
-        .find_all('a')
-        .iter()
-        .filter_map(|position| bfs(&map, *position))
-        .map(|path| path.len())
-        .min()
-        .unwrap();
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Day12.parse(&"Sab\nab\nxyE".into()).unwrap_err().to_string(),
+        "day12.txt:2: uneven row \"ab\""
+    );
+    assert_eq!(
+        Day12
+            .parse(&"Sab\nab1\nxyE".into())
+            .unwrap_err()
+            .to_string(),
+        "day12.txt:2: invalid elevation \"1\""
+    );
+    assert_eq!(
+        Day12.parse(&"aab\nxyE".into()).unwrap_err().to_string(),
+        "day12.txt: missing start position \"S\""
+    );
+}
 
-    println!("shortest path is {}", shortest_path);
+#[test]
+fn test_example_part_1() {
+    assert_eq!(
+        Day12
+            .part_1(&Day12.parse(&crate::file::read_example(12)).unwrap())
+            .unwrap(),
+        31
+    );
 }
 
 #[test]
-fn test_part_2() {
-    part_2()
+fn test_example_part_2() {
+    assert_eq!(
+        Day12
+            .part_2(&Day12.parse(&crate::file::read_example(12)).unwrap())
+            .unwrap(),
+        29
+    );
+}
+
+#[test]
+fn test_no_path() {
+    let map = Day12.parse(&"SbE".into()).unwrap();
+
+    assert_eq!(
+        Day12.part_1(&map).unwrap_err().to_string(),
+        "day12.txt: no path to E"
+    );
 }
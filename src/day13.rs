@@ -1,21 +1,27 @@
 use itertools::Itertools;
 use serde_json::{json, Value};
 
-use crate::file::read_lines;
-
-fn packets() -> Vec<Value> {
-    read_lines("day13.txt")
-        .map(|line| line.unwrap())
-        .chunks(3)
-        .into_iter()
-        .map(|chunk| {
-            chunk
-                .take(2)
-                .map(|x| serde_json::from_str(x.as_str()).unwrap())
-                .collect::<Vec<Value>>()
-        })
-        .flatten()
-        .collect()
+use crate::{
+    error::{Error, Result},
+    input::Input,
+    solution::Solution,
+};
+
+const DAY: u8 = 13;
+
+fn packet(line: &str) -> Result<Value> {
+    fn supported(value: &Value) -> bool {
+        match value {
+            Value::Number(number) => number.is_i64(),
+            Value::Array(values) => values.iter().all(supported),
+            _ => false,
+        }
+    }
+
+    match serde_json::from_str(line) {
+        Ok(value) if supported(&value) => Ok(value),
+        _ => Err(Error::parse(DAY, "invalid packet", line)),
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -72,62 +78,141 @@ fn compare_nodes(left: &Value, right: &Value) -> Compare {
     }
 }
 
-pub fn part_1() {
-    let packets = packets();
-
-    let compared: Vec<_> = packets
-        .iter()
-        .chunks(2)
-        .into_iter()
-        .map(|chunk| chunk.take(2).collect_tuple().unwrap())
-        .map(|(left, right)| compare_nodes(left, right))
-        .zip(1..)
-        .collect();
-
-    println!(
-        "{:?}",
-        compared
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Value>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        let mut packets = vec![];
+
+        for pair in input.paragraphs() {
+            if pair.len() != 2 {
+                let (line, number) = pair[0];
+                return Err(Error::parse(DAY, "expected a pair of packets", line).at_line(number));
+            }
+
+            for (line, number) in pair {
+                packets.push(packet(line).map_err(|error| error.at_line(number))?);
+            }
+        }
+
+        Ok(packets)
+    }
+
+    fn part_1(&self, packets: &Self::Input) -> Result<Self::Answer1> {
+        let compared: Vec<_> = packets
+            .iter()
+            .chunks(2)
+            .into_iter()
+            .map(|chunk| chunk.take(2).collect_tuple().unwrap())
+            .map(|(left, right)| compare_nodes(left, right))
+            .zip(1..)
+            .collect();
+
+        Ok(compared
             .iter()
             .filter(|(compare, _)| *compare == RightOrder)
             .map(|(_, index)| index)
-            .sum::<i64>()
-    );
-}
+            .sum::<i64>())
+    }
 
-#[test]
-fn test_part_1() {
-    part_1();
-}
+    fn part_2(&self, packets: &Self::Input) -> Result<Self::Answer2> {
+        let mut packets = packets.clone();
+        packets.push(json!([[2]]));
+        packets.push(json!([[6]]));
 
-pub fn part_2() {
-    let mut packets = packets();
-    packets.push(json!([[2]]));
-    packets.push(json!([[6]]));
-
-    let ordered: Vec<_> = packets
-        .iter()
-        .sorted_by(|left, right| match compare_nodes(*left, *right) {
-            RightOrder => std::cmp::Ordering::Less,
-            WrongOrder => std::cmp::Ordering::Greater,
-            Continue => std::cmp::Ordering::Equal,
-        })
-        .zip(1..)
-        .collect();
-
-    println!(
-        "{:?}",
-        ordered
+        let ordered: Vec<_> = packets
             .iter()
-            .filter(|(val, _)| match val.to_string().as_str() {
-                "[[2]]" | "[[6]]" => true,
-                _ => false,
+            .sorted_by(|left, right| match compare_nodes(left, right) {
+                RightOrder => std::cmp::Ordering::Less,
+                WrongOrder => std::cmp::Ordering::Greater,
+                Continue => std::cmp::Ordering::Equal,
             })
+            .zip(1..)
+            .collect();
+
+        Ok(ordered
+            .iter()
+            .filter(|(val, _)| matches!(val.to_string().as_str(), "[[2]]" | "[[6]]"))
             .map(|(_, index)| index)
-            .product::<i64>()
+            .product::<i64>())
+    }
+}
+
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_1() {
+    assert_eq!(
+        Day13
+            .part_1(&Day13.parse(&crate::file::read_input(13)).unwrap())
+            .unwrap(),
+        5208
     );
 }
 
 #[test]
+#[ignore = "reads the full puzzle input"]
 fn test_part_2() {
-    part_2();
+    assert_eq!(
+        Day13
+            .part_2(&Day13.parse(&crate::file::read_input(13)).unwrap())
+            .unwrap(),
+        25792
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Day13
+            .parse(&"[1,2]\n[1,\"a\"]".into())
+            .unwrap_err()
+            .to_string(),
+        "day13.txt:2: invalid packet \"[1,\\\"a\\\"]\""
+    );
+    assert_eq!(
+        Day13
+            .parse(&"[1,2]\n[3]\n\n[1]\n[2]\n[3]".into())
+            .unwrap_err()
+            .to_string(),
+        "day13.txt:4: expected a pair of packets \"[1]\""
+    );
+    assert_eq!(
+        Day13
+            .parse(&"[1,2]\n[3]\n\n[[1]\n[]".into())
+            .unwrap_err()
+            .to_string(),
+        "day13.txt:4: invalid packet \"[[1]\""
+    );
+    assert_eq!(
+        Day13.parse(&"[1]\n[2]\n  \n[3]\n[4]".into()).unwrap().len(),
+        4
+    );
+}
+
+#[test]
+fn test_example_part_1() {
+    assert_eq!(
+        Day13
+            .part_1(&Day13.parse(&crate::file::read_example(13)).unwrap())
+            .unwrap(),
+        13
+    );
+}
+
+#[test]
+fn test_example_part_2() {
+    assert_eq!(
+        Day13
+            .part_2(&Day13.parse(&crate::file::read_example(13)).unwrap())
+            .unwrap(),
+        140
+    );
 }
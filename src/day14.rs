@@ -1,52 +1,62 @@
 use std::{collections::HashMap, fmt::Display};
 
-use regex::Regex;
-
 use crate::{
-    file::read_lines,
+    error::{parse_lines, parse_number, Error, Result},
     grid::{range_inclusive, Grid},
+    input::Input,
+    solution::Solution,
 };
 
-fn rocks() -> Vec<Vec<(i64, i64)>> {
-    let regex = Regex::new(r"((\d+),(\d+))( -> )?").unwrap();
-
-    read_lines("day14.txt")
-        .map(|line| line.unwrap())
-        .map(|line| {
-            regex
-                .captures_iter(line.as_str())
-                .map(|x| {
-                    (
-                        x.get(2).unwrap().as_str().parse().unwrap(),
-                        x.get(3).unwrap().as_str().parse().unwrap(),
-                    )
-                })
-                .collect()
-        })
-        .collect()
+const DAY: u8 = 14;
+
+fn rocks(input: &str) -> Result<Vec<Vec<(i64, i64)>>> {
+    parse_lines(input, |line| {
+        line.split(" -> ")
+            .map(|point| {
+                let (x, y) = point
+                    .split_once(',')
+                    .ok_or_else(|| Error::parse(DAY, "invalid point", point))?;
+
+                Ok((parse_number(DAY, x)?, parse_number(DAY, y)?))
+            })
+            .collect()
+    })
 }
 
 #[test]
-fn test_rocks() {
-    let rocks = rocks();
+fn test_rocks_errors() {
+    assert_eq!(
+        rocks("498,4 -> 498,6\n503,4 -> 502")
+            .unwrap_err()
+            .to_string(),
+        "day14.txt:2: invalid point \"502\""
+    );
+    assert_eq!(
+        Day14.parse(&"".into()).unwrap_err().to_string(),
+        "day14.txt: no rock paths \"\""
+    );
+}
 
-    println!("{:?}", rocks);
+#[test]
+fn test_rocks() {
+    assert_eq!(
+        rocks(crate::file::read_example(14).as_str()).unwrap(),
+        vec![
+            vec![(498, 4), (498, 6), (496, 6)],
+            vec![(503, 4), (502, 4), (502, 9), (494, 9)],
+        ]
+    );
 }
 
-#[derive(PartialEq, Clone, Copy)]
+#[derive(PartialEq, Clone, Copy, Default)]
 enum Entity {
+    #[default]
     Air,
     Rock,
     Sand,
     SandSource,
 }
 
-impl Default for Entity {
-    fn default() -> Self {
-        Air
-    }
-}
-
 use Entity::*;
 
 impl Display for Entity {
@@ -60,12 +70,10 @@ impl Display for Entity {
     }
 }
 
+type EndCondition<'a> = dyn Fn(&(i64, i64), &HashMap<(i64, i64), Entity>) -> bool + 'a;
+
 impl Grid<Entity> {
-    fn simulate(
-        &mut self,
-        sand_source: (i64, i64),
-        end_condition: &dyn Fn(&(i64, i64), &HashMap<(i64, i64), Entity>) -> bool,
-    ) -> Option<()> {
+    fn simulate(&mut self, sand_source: (i64, i64), end_condition: &EndCondition) -> Option<()> {
         let mut sand_position = (sand_source.0, sand_source.1);
 
         loop {
@@ -129,38 +137,96 @@ impl From<Vec<Vec<(i64, i64)>>> for Grid<Entity> {
     }
 }
 
-pub fn part_1() {
-    let mut cave: Grid<Entity> = rocks().into();
+pub struct Day14;
 
-    let max_y = cave.max_y();
+impl Solution for Day14 {
+    type Input = Vec<Vec<(i64, i64)>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    while let Some(_) = cave.simulate((500, 0), &|sand_position, _| sand_position.1 > max_y) {}
-    println!("{}\n", cave);
+    fn day(&self) -> u8 {
+        DAY
+    }
 
-    println!("{}", cave.count(Sand))
-}
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        let rocks = rocks(input.as_str())?;
 
-#[test]
-fn test_part_1() {
-    part_1();
-}
+        if rocks.is_empty() {
+            return Err(Error::parse(DAY, "no rock paths", ""));
+        }
 
-pub fn part_2() {
-    let mut cave: Grid<Entity> = rocks().into();
+        Ok(rocks)
+    }
 
-    let floor = cave.max_y() + 2;
+    fn part_1(&self, rocks: &Self::Input) -> Result<Self::Answer1> {
+        let mut cave: Grid<Entity> = rocks.clone().into();
 
-    for x in 300..700 {
-        cave.data.insert((x, floor), Rock);
+        let max_y = cave.max_y();
+
+        while cave
+            .simulate((500, 0), &|sand_position, _| sand_position.1 > max_y)
+            .is_some()
+        {}
+
+        Ok(cave.count(Sand))
     }
 
-    while let Some(_) = cave.simulate((500, 0), &|_, cave| *cave.get(&(500, 0)).unwrap() == Sand) {}
-    println!("{}\n", cave);
+    fn part_2(&self, rocks: &Self::Input) -> Result<Self::Answer2> {
+        let mut cave: Grid<Entity> = rocks.clone().into();
+
+        let floor = cave.max_y() + 2;
+
+        for x in 300..700 {
+            cave.data.insert((x, floor), Rock);
+        }
 
-    println!("{}", cave.count(Sand))
+        while cave
+            .simulate((500, 0), &|_, cave| *cave.get(&(500, 0)).unwrap() == Sand)
+            .is_some()
+        {}
+
+        Ok(cave.count(Sand))
+    }
 }
 
 #[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_1() {
+    assert_eq!(
+        Day14
+            .part_1(&Day14.parse(&crate::file::read_input(14)).unwrap())
+            .unwrap(),
+        1061
+    );
+}
+
+#[test]
+#[ignore = "reads the full puzzle input"]
 fn test_part_2() {
-    part_2();
+    assert_eq!(
+        Day14
+            .part_2(&Day14.parse(&crate::file::read_input(14)).unwrap())
+            .unwrap(),
+        25055
+    );
+}
+
+#[test]
+fn test_example_part_1() {
+    assert_eq!(
+        Day14
+            .part_1(&Day14.parse(&crate::file::read_example(14)).unwrap())
+            .unwrap(),
+        24
+    );
+}
+
+#[test]
+fn test_example_part_2() {
+    assert_eq!(
+        Day14
+            .part_2(&Day14.parse(&crate::file::read_example(14)).unwrap())
+            .unwrap(),
+        93
+    );
 }
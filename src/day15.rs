@@ -3,21 +3,23 @@ use std::{collections::HashMap, fmt::Display};
 use itertools::Itertools;
 use regex::Regex;
 
-use crate::{file::read_lines, grid::Grid};
-
-#[derive(Copy, Clone, PartialEq, Debug)]
-enum Entity {
+use crate::{
+    error::{parse_lines, parse_number, Error, Result},
+    grid::Grid,
+    input::Input,
+    solution::Solution,
+};
+
+const DAY: u8 = 15;
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum Entity {
+    #[default]
     Air,
     Sensor(i64),
     Beacon,
 }
 
-impl Default for Entity {
-    fn default() -> Self {
-        Air
-    }
-}
-
 impl Display for Entity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -30,38 +32,43 @@ impl Display for Entity {
 
 use Entity::*;
 
-fn input() -> Vec<((i64, i64), (i64, i64))> {
+type SensorReport = ((i64, i64), (i64, i64));
+
+fn positions(input: &str) -> Result<Vec<SensorReport>> {
     let regex =
         Regex::new(r"Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)")
             .unwrap();
 
-    read_lines("day15.txt")
-        .map(|line| line.unwrap())
-        .map(|line| {
-            regex
-                .captures_iter(line.as_str())
-                .map(|x| {
-                    (
-                        (
-                            x.get(1).unwrap().as_str().parse().unwrap(),
-                            x.get(2).unwrap().as_str().parse().unwrap(),
-                        ),
-                        (
-                            x.get(3).unwrap().as_str().parse().unwrap(),
-                            x.get(4).unwrap().as_str().parse().unwrap(),
-                        ),
-                    )
-                })
-                .collect::<Vec<((i64, i64), (i64, i64))>>()
-        })
-        .flatten()
-        .collect()
+    parse_lines(input, |line| {
+        let x = regex
+            .captures(line)
+            .ok_or_else(|| Error::parse(DAY, "invalid sensor report", line))?;
+        let number = |index| parse_number(DAY, x.get(index).unwrap().as_str());
+
+        Ok(((number(1)?, number(2)?), (number(3)?, number(4)?)))
+    })
+}
+
+#[test]
+fn test_positions_errors() {
+    assert_eq!(
+        positions("Sensor at x=2, y=18: closest beacon at x=-2, y=15")
+            .unwrap_err()
+            .to_string(),
+        "day15.txt:1: invalid sensor report \"Sensor at x=2, y=18: closest beacon at x=-2, y=15\""
+    );
+    assert_eq!(
+        Day15::PUZZLE.parse(&"".into()).err().unwrap().to_string(),
+        "day15.txt: no sensors \"\""
+    );
 }
 
 #[test]
-fn test_input() {
-    let input = input();
-    println!("{:?}", input);
+fn test_positions() {
+    let positions = positions(crate::file::read_example(15).as_str()).unwrap();
+
+    assert_eq!(positions.len(), 14);
+    assert_eq!(positions[0], ((2, 18), (-2, 15)));
 }
 
 fn manhattan_distance(a: (i64, i64), b: (i64, i64)) -> i64 {
@@ -109,17 +116,6 @@ impl Grid<Entity> {
     }
 }
 
-pub fn part_1() {
-    let grid: Grid<Entity> = input().into();
-    let row = grid.beaconless(10);
-    println!("{:?}", row);
-}
-
-#[test]
-fn test_part_1() {
-    part_1();
-}
-
 fn cross_product(a: (i64, i64), b: (i64, i64)) -> i64 {
     (a.0 * b.1) - (a.1 * b.0)
 }
@@ -128,6 +124,7 @@ fn line_from(a: (i64, i64), b: (i64, i64)) -> (i64, i64) {
     (a.0 - b.0, a.1 - b.1)
 }
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, PartialEq, Hash, Eq)]
 enum Intersect {
     Colinear,
@@ -155,7 +152,7 @@ fn intersect(p: (i64, i64), p2: (i64, i64), q: (i64, i64), q2: (i64, i64)) -> In
     let u = numerator as f64 / denominator as f64;
     let t = cross_product(line_from(q, p), s) as f64 / denominator as f64;
 
-    if t >= 0.0 && t <= 1.0 && u >= 0.0 && u <= 1.0 {
+    if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
         Intersect::Intersect((
             (p.0 as f64 + t * r.0 as f64).floor() as i64,
             (p.1 as f64 + t * r.1 as f64).floor() as i64,
@@ -170,7 +167,7 @@ impl Grid<Entity> {
         let lines: Vec<_> = self
             .data
             .iter()
-            .map(|(position, entity)| {
+            .flat_map(|(position, entity)| {
                 if let Sensor(distance) = *entity {
                     let distance = distance + 1;
                     vec![
@@ -195,56 +192,145 @@ impl Grid<Entity> {
                     vec![]
                 }
             })
-            .flatten()
             .filter(|line| line.0 .0 < upper_limit && line.0 .0 > 0)
             .collect();
 
         // println!("{:?}", lines);
 
-        let counts: _ = lines
+        let counts = lines
             .clone()
             .iter()
-            .map(|x| {
+            .flat_map(|x| {
                 lines
                     .clone()
                     .iter()
                     .map(|y| (intersect(x.0, x.1, y.0, y.1), (*x, *y)))
-                    .filter(|(x, _)| {
-                        if let Intersect::Intersect(_) = *x {
-                            true
-                        } else {
-                            false
-                        }
-                    })
+                    .filter(|(x, _)| matches!(*x, Intersect::Intersect(_)))
                     .collect::<Vec<(_, _)>>()
             })
-            .flatten()
             .counts_by(|a| a.0);
 
         /*for x in counts.iter().sorted_by(|a, b| a.1.cmp(b.1)) {
             println!("{:?}", x);
         }*/
 
-        let max = counts.iter().max_by_key(|(_, count)| **count).unwrap();
+        counts
+            .iter()
+            .filter_map(|(intersect, count)| match *intersect {
+                Intersect::Intersect(position) => Some((position, *count)),
+                _ => None,
+            })
+            .sorted_by_key(|(position, count)| (std::cmp::Reverse(*count), *position))
+            .map(|(position, _)| position)
+            .find(|position| {
+                (0..=upper_limit).contains(&position.0)
+                    && (0..=upper_limit).contains(&position.1)
+                    && !self.in_sensor_range(*position)
+            })
+    }
+
+    fn in_sensor_range(&self, position: (i64, i64)) -> bool {
+        self.data.iter().any(|(sensor, entity)| match *entity {
+            Sensor(distance) => manhattan_distance(*sensor, position) <= distance,
+            _ => false,
+        })
+    }
+}
+
+/// The puzzle asks about a different row and search area for the example than
+/// for the real input, so both are part of the solution.
+pub struct Day15 {
+    pub row: i64,
+    pub search_limit: i64,
+}
+
+impl Day15 {
+    pub const PUZZLE: Day15 = Day15 {
+        row: 2000000,
+        search_limit: 4000000,
+    };
+
+    #[cfg(test)]
+    pub const EXAMPLE: Day15 = Day15 {
+        row: 10,
+        search_limit: 20,
+    };
+}
 
-        if let Intersect::Intersect(position) = *max.0 {
-            Some(position)
-        } else {
-            None
+impl Solution for Day15 {
+    type Input = Grid<Entity>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        let positions = positions(input.as_str())?;
+
+        if positions.is_empty() {
+            return Err(Error::parse(DAY, "no sensors", ""));
         }
+
+        Ok(positions.into())
+    }
+
+    fn part_1(&self, grid: &Self::Input) -> Result<Self::Answer1> {
+        Ok(grid.beaconless(self.row))
+    }
+
+    fn part_2(&self, grid: &Self::Input) -> Result<Self::Answer2> {
+        let position = grid
+            .hidden_beacon(self.search_limit)
+            .ok_or_else(|| Error::no_answer(DAY, "no hidden beacon"))?;
+
+        Ok(position.0 * 4000000 + position.1)
     }
 }
 
-pub fn part_2() {
-    let grid: Grid<Entity> = input().into();
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_1() {
+    let day = Day15::PUZZLE;
 
-    let position = grid.hidden_beacon(4000000).unwrap();
-    println!("{:?}", position);
-    let tuning_frequency = position.0 * 4000000 + position.1;
-    println!("{:?}", tuning_frequency);
+    assert_eq!(
+        day.part_1(&day.parse(&crate::file::read_input(15)).unwrap())
+            .unwrap(),
+        4861076
+    );
 }
 
 #[test]
+#[ignore = "reads the full puzzle input"]
 fn test_part_2() {
-    part_2();
+    let day = Day15::PUZZLE;
+
+    assert_eq!(
+        day.part_2(&day.parse(&crate::file::read_input(15)).unwrap())
+            .unwrap(),
+        10649103160102
+    );
+}
+
+#[test]
+fn test_example_part_1() {
+    let day = Day15::EXAMPLE;
+
+    assert_eq!(
+        day.part_1(&day.parse(&crate::file::read_example(15)).unwrap())
+            .unwrap(),
+        26
+    );
+}
+
+#[test]
+fn test_example_part_2() {
+    let day = Day15::EXAMPLE;
+
+    assert_eq!(
+        day.part_2(&day.parse(&crate::file::read_example(15)).unwrap())
+            .unwrap(),
+        56000011
+    );
 }
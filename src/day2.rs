@@ -1,165 +1,744 @@
-use core::panic;
-use std::io::Error;
+use itertools::Itertools;
+use serde_json::{json, Value};
 
-use crate::file::read_lines;
+use crate::{
+    error::{parse_lines, Error, Result},
+    input::Input,
+    solution::Solution,
+};
 
-#[derive(Debug, Clone, PartialEq)]
-enum Hand {
-    Rock,
-    Paper,
-    Scissor,
+const DAY: u8 = 2;
+
+/// A shape, by its position in the cyclic order of a rule set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hand(pub usize);
+
+impl Hand {
+    pub const ROCK: Hand = Hand(0);
+    pub const PAPER: Hand = Hand(1);
+    pub const SCISSOR: Hand = Hand(2);
 }
 
-#[derive(Debug, Clone, PartialEq)]
-enum Outcome {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
     Win,
     Draw,
     Lose,
 }
 
-fn line_to_hands(line: &Result<String, Error>) -> (Hand, Hand) {
-    let hands: Vec<&str> = match line {
-        Ok(it) => it,
-        Err(_err) => panic!("should not happen"),
+/// A cyclic game with an odd number of shapes, where every shape beats the
+/// half of the other shapes that come right before it, wrapping around, and
+/// loses to the half that come right after it. Rock, paper, scissors is the
+/// game with three shapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// The name and score of every shape, in cyclic order.
+    pub shapes: &'static [(&'static str, i32)],
+    pub win: i32,
+    pub draw: i32,
+    pub lose: i32,
+}
+
+impl Rules {
+    /// The rules of the puzzle.
+    pub const ROCK_PAPER_SCISSORS: Rules = Rules {
+        shapes: &[("rock", 1), ("paper", 2), ("scissors", 3)],
+        win: 6,
+        draw: 3,
+        lose: 0,
+    };
+
+    pub const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: Rules = Rules {
+        shapes: &[
+            ("spock", 1),
+            ("paper", 2),
+            ("lizard", 3),
+            ("scissors", 4),
+            ("rock", 5),
+        ],
+        win: 6,
+        draw: 3,
+        lose: 0,
+    };
+
+    fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    /// Makes sure there are at least three shapes and an odd number of them,
+    /// so that every pair of different shapes has a winner.
+    pub fn check(&self) -> Result<()> {
+        if self.len() < 3 || self.len().is_multiple_of(2) {
+            return Err(Error::Usage(format!(
+                "rules need an odd number of at least 3 shapes, got {}",
+                self.len()
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn hands(&self) -> impl Iterator<Item = Hand> {
+        (0..self.len()).map(Hand)
+    }
+
+    pub fn outcome(&self, (other, my_hand): (Hand, Hand)) -> Outcome {
+        let distance = (my_hand.0 + self.len() - other.0) % self.len();
+
+        if distance == 0 {
+            Outcome::Draw
+        } else if distance <= self.len() / 2 {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> i32 {
+        match outcome {
+            Outcome::Win => self.win,
+            Outcome::Draw => self.draw,
+            Outcome::Lose => self.lose,
+        }
+    }
+
+    pub fn score(&self, (other, my_hand): (Hand, Hand)) -> i32 {
+        self.shapes[my_hand.0].1 + self.outcome_score(self.outcome((other, my_hand)))
+    }
+
+    /// The hand to play against `other` to end with `outcome`. When more than
+    /// one hand does, the one that scores the most is played. There is always
+    /// such a hand under rules that pass [`Rules::check`].
+    pub fn outcome_to_hands(&self, (other, outcome): (Hand, Outcome)) -> Option<(Hand, Hand)> {
+        self.hands()
+            .filter(|&my_hand| self.outcome((other, my_hand)) == outcome)
+            .max_by_key(|&my_hand| self.score((other, my_hand)))
+            .map(|my_hand| (other, my_hand))
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::ROCK_PAPER_SCISSORS
+    }
+}
+
+#[test]
+fn test_outcome() {
+    let rules = Rules::default();
+
+    assert_eq!(rules.outcome((Hand::SCISSOR, Hand::ROCK)), Outcome::Win);
+    assert_eq!(rules.outcome((Hand::ROCK, Hand::PAPER)), Outcome::Win);
+    assert_eq!(rules.outcome((Hand::PAPER, Hand::ROCK)), Outcome::Lose);
+    assert_eq!(rules.outcome((Hand::PAPER, Hand::PAPER)), Outcome::Draw);
+    assert_eq!(
+        rules.outcome_to_hands((Hand::SCISSOR, Outcome::Win)),
+        Some((Hand::SCISSOR, Hand::ROCK))
+    );
+}
+
+#[test]
+fn test_check_rules() {
+    assert!(Rules::ROCK_PAPER_SCISSORS.check().is_ok());
+    assert!(Rules::ROCK_PAPER_SCISSORS_LIZARD_SPOCK.check().is_ok());
+
+    for shapes in [
+        &[][..],
+        &[("rock", 1)],
+        &[("rock", 1), ("paper", 2), ("scissors", 3), ("well", 4)],
+    ] {
+        let rules = Rules {
+            shapes,
+            ..Rules::default()
+        };
+
+        assert_eq!(
+            rules.check().unwrap_err().to_string(),
+            format!(
+                "rules need an odd number of at least 3 shapes, got {}",
+                shapes.len()
+            )
+        );
+        assert!(Mapping::PUZZLE.check(&rules).is_err());
+        assert!(Day2 {
+            rules,
+            mapping: Mapping::PUZZLE,
+        }
+        .parse(&crate::file::read_example(2))
+        .is_err());
     }
-    .split(' ')
-    .collect();
 
-    let left = match hands[0] {
-        "A" => Hand::Rock,
-        "B" => Hand::Paper,
-        "C" => Hand::Scissor,
-        _ => panic!("should not happen"),
+    let rules = Rules {
+        shapes: &[("rock", 1), ("paper", 2)],
+        ..Rules::default()
     };
+    assert_eq!(rules.outcome_to_hands((Hand::ROCK, Outcome::Lose)), None);
+}
+
+#[test]
+fn test_rock_paper_scissors_lizard_spock() {
+    let rules = Rules::ROCK_PAPER_SCISSORS_LIZARD_SPOCK;
+    let hand = |name| Hand(rules.shapes.iter().position(|(n, _)| *n == name).unwrap());
 
-    let right = match hands[1] {
-        "X" => Hand::Rock,
-        "Y" => Hand::Paper,
-        "Z" => Hand::Scissor,
-        _ => panic!("should not happen"),
+    for (winner, loser) in [
+        ("scissors", "paper"),
+        ("paper", "rock"),
+        ("rock", "lizard"),
+        ("lizard", "spock"),
+        ("spock", "scissors"),
+        ("scissors", "lizard"),
+        ("lizard", "paper"),
+        ("paper", "spock"),
+        ("spock", "rock"),
+        ("rock", "scissors"),
+    ] {
+        assert_eq!(rules.outcome((hand(loser), hand(winner))), Outcome::Win);
+        assert_eq!(rules.outcome((hand(winner), hand(loser))), Outcome::Lose);
+    }
+
+    assert_eq!(
+        rules.outcome_to_hands((hand("spock"), Outcome::Win)),
+        Some((hand("spock"), hand("lizard")))
+    );
+}
+
+fn split_line(line: &str) -> Result<(&str, &str)> {
+    line.split_once(' ')
+        .ok_or_else(|| Error::parse(DAY, "expected two columns", line))
+}
+
+/// How the columns of a strategy guide are read. Every symbol is a single
+/// character, and the symbols are listed in the order of what they stand for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mapping<'a> {
+    /// The opponent's hands, in the cyclic order of the rules.
+    pub opponent: &'a str,
+    /// The second column read as my hands, in the cyclic order of the rules.
+    pub hands: &'a str,
+    /// The second column read as outcomes: lose, draw and win.
+    pub outcomes: &'a str,
+}
+
+impl Mapping<'_> {
+    /// The reading of the puzzle.
+    pub const PUZZLE: Mapping<'static> = Mapping {
+        opponent: "ABC",
+        hands: "XYZ",
+        outcomes: "XYZ",
     };
 
-    (left, right)
+    /// Makes sure the mapping has one distinct symbol for everything it stands
+    /// for under `rules`, which have to be valid themselves.
+    pub fn check(&self, rules: &Rules) -> Result<()> {
+        rules.check()?;
+
+        for (name, symbols, count) in [
+            ("opponent", self.opponent, rules.len()),
+            ("hands", self.hands, rules.len()),
+            ("outcomes", self.outcomes, 3),
+        ] {
+            if symbols.chars().count() != count || !symbols.chars().all_unique() {
+                return Err(Error::Usage(format!(
+                    "{} needs {} distinct symbols, got {:?}",
+                    name, count, symbols
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The position of `column` in `symbols`, if it is one of them.
+fn symbol(symbols: &str, column: &str) -> Option<usize> {
+    let mut chars = column.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(column), None) => symbols.chars().position(|symbol| symbol == column),
+        _ => None,
+    }
+}
+
+fn opponent_hand(rules: &Rules, mapping: &Mapping, column: &str) -> Result<Hand> {
+    symbol(mapping.opponent, column)
+        .filter(|&hand| hand < rules.len())
+        .map(Hand)
+        .ok_or_else(|| Error::parse(DAY, "unknown opponent hand", column))
+}
+
+fn my_hand(rules: &Rules, mapping: &Mapping, column: &str) -> Result<Hand> {
+    symbol(mapping.hands, column)
+        .filter(|&hand| hand < rules.len())
+        .map(Hand)
+        .ok_or_else(|| Error::parse(DAY, "unknown hand", column))
+}
+
+fn outcome(mapping: &Mapping, column: &str) -> Result<Outcome> {
+    match symbol(mapping.outcomes, column) {
+        Some(0) => Ok(Outcome::Lose),
+        Some(1) => Ok(Outcome::Draw),
+        Some(2) => Ok(Outcome::Win),
+        _ => Err(Error::parse(DAY, "unknown outcome", column)),
+    }
+}
+
+/// Reads the opponent's hand, leaving the second column to be read by each
+/// part. It only has to be a symbol of one of the two readings.
+fn line_to_round(rules: &Rules, mapping: &Mapping, line: &str) -> Result<Round> {
+    let (left, right) = split_line(line)?;
+
+    let column = right
+        .chars()
+        .exactly_one()
+        .ok()
+        .filter(|&column| mapping.hands.contains(column) || mapping.outcomes.contains(column))
+        .ok_or_else(|| Error::parse(DAY, "unknown symbol", right))?;
+
+    Ok((opponent_hand(rules, mapping, left)?, column))
 }
 
 #[test]
-fn test_line_to_hands() {
+fn test_line_to_round() {
+    let rules = Rules::default();
+    let mapping = Mapping::PUZZLE;
+
     assert_eq!(
-        line_to_hands(&Ok("A Y".to_string())),
-        (Hand::Rock, Hand::Paper)
+        line_to_round(&rules, &mapping, "A Y").unwrap(),
+        (Hand::ROCK, 'Y')
     );
     assert_eq!(
-        line_to_hands(&Ok("B X".to_string())),
-        (Hand::Paper, Hand::Rock)
+        line_to_round(&rules, &mapping, "C Z").unwrap(),
+        (Hand::SCISSOR, 'Z')
     );
     assert_eq!(
-        line_to_hands(&Ok("C Z".to_string())),
-        (Hand::Scissor, Hand::Scissor)
+        line_to_round(&rules, &mapping, "D Z")
+            .unwrap_err()
+            .to_string(),
+        "day2.txt: unknown opponent hand \"D\""
     );
+    assert_eq!(
+        line_to_round(&rules, &mapping, "A W")
+            .unwrap_err()
+            .to_string(),
+        "day2.txt: unknown symbol \"W\""
+    );
+    assert!(line_to_round(&rules, &mapping, "A YY").is_err());
+    assert!(line_to_round(&rules, &mapping, "AY").is_err());
 }
 
-fn hands_to_outcome((other, my_hand): (Hand, Hand)) -> Outcome {
-    match (my_hand, other) {
-        (Hand::Rock, Hand::Scissor) => Outcome::Win,
-        (Hand::Scissor, Hand::Paper) => Outcome::Win,
-        (Hand::Paper, Hand::Rock) => Outcome::Win,
-        (Hand::Scissor, Hand::Rock) => Outcome::Lose,
-        (Hand::Paper, Hand::Scissor) => Outcome::Lose,
-        (Hand::Rock, Hand::Paper) => Outcome::Lose,
-        (Hand::Rock, Hand::Rock) => Outcome::Draw,
-        (Hand::Paper, Hand::Paper) => Outcome::Draw,
-        (Hand::Scissor, Hand::Scissor) => Outcome::Draw,
-    }
-}
+#[test]
+fn test_my_hand_and_outcome() {
+    let rules = Rules::default();
+    let mapping = Mapping::PUZZLE;
+
+    assert_eq!(my_hand(&rules, &mapping, "X").unwrap(), Hand::ROCK);
+    assert_eq!(my_hand(&rules, &mapping, "Z").unwrap(), Hand::SCISSOR);
+    assert_eq!(outcome(&mapping, "X").unwrap(), Outcome::Lose);
+    assert_eq!(outcome(&mapping, "Y").unwrap(), Outcome::Draw);
+    assert_eq!(outcome(&mapping, "Z").unwrap(), Outcome::Win);
 
-fn hands_to_score((other, my_hand): (Hand, Hand)) -> i32 {
-    let shape_score = match my_hand {
-        Hand::Rock => 1,
-        Hand::Paper => 2,
-        Hand::Scissor => 3,
+    let mapping = Mapping {
+        hands: "ZXY",
+        outcomes: "ZYX",
+        ..Mapping::PUZZLE
     };
+    assert_eq!(my_hand(&rules, &mapping, "Z").unwrap(), Hand::ROCK);
+    assert_eq!(outcome(&mapping, "Z").unwrap(), Outcome::Lose);
+}
+
+/// A line of the guide: the opponent's hand, and the symbol of the second
+/// column, which part 1 reads as my hand and part 2 as the outcome to end
+/// with.
+pub type Round = (Hand, char);
+
+pub struct Day2<'a> {
+    pub rules: Rules,
+    pub mapping: Mapping<'a>,
+}
 
-    let outcome_score = match hands_to_outcome((other, my_hand)) {
-        Outcome::Win => 6,
-        Outcome::Draw => 3,
-        Outcome::Lose => 0,
+impl Day2<'_> {
+    /// The hands played in every round, with the second column read as hands
+    /// for part 1 and as outcomes for part 2.
+    pub fn games(&self, rounds: &[Round], part: u8) -> Result<Vec<(Hand, Hand)>> {
+        if !(1..=2).contains(&part) {
+            return Err(Error::UnknownPart(part));
+        }
+
+        rounds
+            .iter()
+            .zip(1..)
+            .map(|(&(other, column), number)| {
+                let column = column.to_string();
+                let game = match part {
+                    1 => my_hand(&self.rules, &self.mapping, &column).map(|hand| (other, hand)),
+                    _ => outcome(&self.mapping, &column).and_then(|outcome| {
+                        self.rules
+                            .outcome_to_hands((other, outcome))
+                            .ok_or_else(|| {
+                                Error::no_answer(DAY, &format!("no hand ends in a {:?}", outcome))
+                            })
+                    }),
+                };
+
+                game.map_err(|error| error.at_line(number))
+            })
+            .collect()
+    }
+
+    pub const PUZZLE: Day2<'static> = Day2 {
+        rules: Rules::ROCK_PAPER_SCISSORS,
+        mapping: Mapping::PUZZLE,
     };
 
-    shape_score + outcome_score
+    /// The total score of the guide, with the second column read for `part`.
+    pub fn score(&self, rounds: &[Round], part: u8) -> Result<i32> {
+        Ok(self
+            .games(rounds, part)?
+            .into_iter()
+            .map(|hands| self.rules.score(hands))
+            .sum())
+    }
+
+    /// Scores `part` of the guide under every order of the symbols of the
+    /// second column, as read for that part. Each reading comes with the
+    /// symbols in the order of what they stand for.
+    pub fn readings(&self, rounds: &[Round], part: u8) -> Result<Vec<(String, i32)>> {
+        let symbols = match part {
+            1 => self.mapping.hands,
+            2 => self.mapping.outcomes,
+            _ => return Err(Error::UnknownPart(part)),
+        };
+
+        symbols
+            .chars()
+            .permutations(symbols.chars().count())
+            .map(|reading| {
+                let reading: String = reading.into_iter().collect();
+                let mapping = match part {
+                    1 => Mapping {
+                        hands: &reading,
+                        ..self.mapping
+                    },
+                    _ => Mapping {
+                        outcomes: &reading,
+                        ..self.mapping
+                    },
+                };
+                let day = Day2 {
+                    rules: self.rules,
+                    mapping,
+                };
+
+                let score = day.score(rounds, part)?;
+
+                Ok((reading, score))
+            })
+            .collect()
+    }
+}
+
+impl Default for Day2<'_> {
+    fn default() -> Self {
+        Day2::PUZZLE
+    }
 }
 
-pub fn part_1() {
-    let total_score: i32 = read_lines("day2.txt")
-        .map(|line| line_to_hands(&line))
-        .map(hands_to_score)
-        .sum();
+/// How the guide fares against one of the opponent's hands, next to the best
+/// and worst scores that could have been had against it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Matchup {
+    pub other: Hand,
+    pub rounds: usize,
+    pub score: i32,
+    pub best: i32,
+    pub worst: i32,
+}
 
-    println!("{}", total_score);
+/// A breakdown of the score of the guide, as read for one part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub part: u8,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub shape_score: i32,
+    pub outcome_score: i32,
+    /// One per opponent hand, in the cyclic order of the rules.
+    pub matchups: Vec<Matchup>,
 }
 
-fn line_to_hand_and_outcome(line: &Result<String, Error>) -> (Hand, Outcome) {
-    let hand_and_outcome: Vec<&str> = match line {
-        Ok(it) => it,
-        Err(_err) => panic!("should not happen"),
+impl Report {
+    pub fn new(rules: &Rules, part: u8, games: &[(Hand, Hand)]) -> Self {
+        let outcomes: Vec<_> = games.iter().map(|&hands| rules.outcome(hands)).collect();
+        let count = |outcome| outcomes.iter().filter(|&&o| o == outcome).count();
+
+        let matchups = rules
+            .hands()
+            .map(|other| {
+                let rounds = games.iter().filter(|(o, _)| *o == other).count();
+                let scores: Vec<_> = rules
+                    .hands()
+                    .map(|my_hand| rules.score((other, my_hand)))
+                    .collect();
+
+                Matchup {
+                    other,
+                    rounds,
+                    score: games
+                        .iter()
+                        .filter(|(o, _)| *o == other)
+                        .map(|&hands| rules.score(hands))
+                        .sum(),
+                    best: scores.iter().max().unwrap() * rounds as i32,
+                    worst: scores.iter().min().unwrap() * rounds as i32,
+                }
+            })
+            .collect();
+
+        Report {
+            part,
+            wins: count(Outcome::Win),
+            draws: count(Outcome::Draw),
+            losses: count(Outcome::Lose),
+            shape_score: games
+                .iter()
+                .map(|(_, my_hand)| rules.shapes[my_hand.0].1)
+                .sum(),
+            outcome_score: outcomes.iter().map(|&o| rules.outcome_score(o)).sum(),
+            matchups,
+        }
     }
-    .split(' ')
-    .collect();
 
-    let left = match hand_and_outcome[0] {
-        "A" => Hand::Rock,
-        "B" => Hand::Paper,
-        "C" => Hand::Scissor,
-        _ => panic!("should not happen"),
-    };
+    pub fn score(&self) -> i32 {
+        self.shape_score + self.outcome_score
+    }
 
-    let outcome = match hand_and_outcome[1] {
-        "X" => Outcome::Lose,
-        "Y" => Outcome::Draw,
-        "Z" => Outcome::Win,
-        _ => panic!("should not happen"),
-    };
+    pub fn to_json(&self, rules: &Rules) -> Value {
+        json!({
+            "part": self.part,
+            "score": self.score(),
+            "wins": self.wins,
+            "draws": self.draws,
+            "losses": self.losses,
+            "shape_score": self.shape_score,
+            "outcome_score": self.outcome_score,
+            "opponents": self.matchups.iter().map(|matchup| json!({
+                "hand": rules.shapes[matchup.other.0].0,
+                "rounds": matchup.rounds,
+                "score": matchup.score,
+                "best": matchup.best,
+                "worst": matchup.worst,
+            })).collect::<Vec<_>>(),
+        })
+    }
+}
+
+pub fn print_reports(rules: &Rules, reports: &[Report]) {
+    for report in reports {
+        println!("Part {}: {}", report.part, report.score());
+        println!(
+            "  {} won, {} drawn, {} lost; {} from shapes, {} from outcomes",
+            report.wins, report.draws, report.losses, report.shape_score, report.outcome_score
+        );
+        println!(
+            "  {:<8}  {:>6}  {:>6}  {:>6}  {:>6}",
+            "Opponent", "Rounds", "Guide", "Best", "Worst"
+        );
+
+        for matchup in report.matchups.iter() {
+            println!(
+                "  {:<8}  {:>6}  {:>6}  {:>6}  {:>6}",
+                rules.shapes[matchup.other.0].0,
+                matchup.rounds,
+                matchup.score,
+                matchup.best,
+                matchup.worst
+            );
+        }
+    }
+}
+
+impl Solution for Day2<'_> {
+    type Input = Vec<Round>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        self.mapping.check(&self.rules)?;
 
-    (left, outcome)
+        parse_lines(input.as_str(), |line| {
+            line_to_round(&self.rules, &self.mapping, line)
+        })
+    }
+
+    fn part_1(&self, rounds: &Self::Input) -> Result<Self::Answer1> {
+        self.score(rounds, 1)
+    }
+
+    fn part_2(&self, rounds: &Self::Input) -> Result<Self::Answer2> {
+        self.score(rounds, 2)
+    }
 }
 
 #[test]
-fn test_line_to_hand_and_outcome() {
+#[ignore = "reads the full puzzle input"]
+fn test_part_1() {
+    let day = Day2::PUZZLE;
+
     assert_eq!(
-        line_to_hand_and_outcome(&Ok("A Y".to_string())),
-        (Hand::Rock, Outcome::Draw)
+        day.part_1(&day.parse(&crate::file::read_input(2)).unwrap())
+            .unwrap(),
+        12586
     );
+}
+
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_2() {
+    let day = Day2::PUZZLE;
+
     assert_eq!(
-        line_to_hand_and_outcome(&Ok("B X".to_string())),
-        (Hand::Paper, Outcome::Lose)
+        day.part_2(&day.parse(&crate::file::read_input(2)).unwrap())
+            .unwrap(),
+        13193
     );
+}
+
+#[test]
+fn test_example_part_1() {
+    let day = Day2::PUZZLE;
+
+    assert_eq!(
+        day.part_1(&day.parse(&crate::file::read_example(2)).unwrap())
+            .unwrap(),
+        15
+    );
+}
+
+#[test]
+fn test_example_part_2() {
+    let day = Day2::PUZZLE;
+
     assert_eq!(
-        line_to_hand_and_outcome(&Ok("C Z".to_string())),
-        (Hand::Scissor, Outcome::Win)
+        day.part_2(&day.parse(&crate::file::read_example(2)).unwrap())
+            .unwrap(),
+        12
     );
 }
 
-fn outcome_to_hands((other, outcome): (Hand, Outcome)) -> (Hand, Hand) {
-    let my_hand = match (&other, &outcome) {
-        (Hand::Rock, Outcome::Win) => Hand::Paper,
-        (Hand::Rock, Outcome::Draw) => Hand::Rock,
-        (Hand::Rock, Outcome::Lose) => Hand::Scissor,
-        (Hand::Paper, Outcome::Win) => Hand::Scissor,
-        (Hand::Paper, Outcome::Draw) => Hand::Paper,
-        (Hand::Paper, Outcome::Lose) => Hand::Rock,
-        (Hand::Scissor, Outcome::Win) => Hand::Rock,
-        (Hand::Scissor, Outcome::Draw) => Hand::Scissor,
-        (Hand::Scissor, Outcome::Lose) => Hand::Paper,
+#[test]
+fn test_custom_scores() {
+    let day = Day2 {
+        rules: Rules {
+            shapes: &[("rock", 0), ("paper", 0), ("scissors", 0)],
+            win: 1,
+            draw: 0,
+            lose: -1,
+        },
+        mapping: Mapping::PUZZLE,
+    };
+    let rounds = day.parse(&crate::file::read_example(2)).unwrap();
+
+    assert_eq!(day.part_1(&rounds).unwrap(), 0);
+    assert_eq!(day.part_2(&rounds).unwrap(), 0);
+}
+
+#[test]
+fn test_rock_paper_scissors_lizard_spock_guide() {
+    let day = Day2 {
+        rules: Rules::ROCK_PAPER_SCISSORS_LIZARD_SPOCK,
+        mapping: Mapping {
+            opponent: "ABCDE",
+            hands: "VWXYZ",
+            outcomes: "XYZ",
+        },
     };
 
-    (other, my_hand)
+    let rounds = day.parse(&"A X\nB Y\nC Z".into()).unwrap();
+    assert_eq!(day.part_1(&rounds).unwrap(), 9 + 10 + 11);
+    assert_eq!(day.part_2(&rounds).unwrap(), 5 + 5 + 11);
+
+    let rounds = day.parse(&"A V\nB X\nC Z".into()).unwrap();
+    assert_eq!(day.part_1(&rounds).unwrap(), 4 + 9 + 11);
+    assert_eq!(
+        day.part_2(&rounds).unwrap_err().to_string(),
+        "day2.txt:1: unknown outcome \"V\""
+    );
 }
 
-pub fn part_2() {
-    let total_score: i32 = read_lines("day2.txt")
-        .map(|line| line_to_hand_and_outcome(&line))
-        .map(outcome_to_hands)
-        .map(hands_to_score)
-        .sum();
+#[test]
+fn test_readings() {
+    let day = Day2::PUZZLE;
+    let rounds = day.parse(&crate::file::read_example(2)).unwrap();
+    let readings = day.readings(&rounds, 1).unwrap();
+
+    assert_eq!(readings.len(), 6);
+    assert!(readings.contains(&("XYZ".to_string(), 15)));
+    assert_eq!(
+        readings.iter().max_by_key(|(_, score)| *score).unwrap(),
+        &("ZYX".to_string(), 24)
+    );
+    assert_eq!(
+        readings.iter().min_by_key(|(_, score)| *score).unwrap(),
+        &("XZY".to_string(), 6)
+    );
+
+    let readings = day.readings(&rounds, 2).unwrap();
+    assert!(readings.contains(&("XYZ".to_string(), 12)));
+
+    assert!(matches!(
+        day.readings(&rounds, 7),
+        Err(Error::UnknownPart(7))
+    ));
+    assert!(matches!(day.games(&rounds, 7), Err(Error::UnknownPart(7))));
+}
+
+#[test]
+fn test_check_mapping() {
+    let rules = Rules::default();
+
+    assert!(Mapping::PUZZLE.check(&rules).is_ok());
+    assert_eq!(
+        Mapping {
+            hands: "XXZ",
+            ..Mapping::PUZZLE
+        }
+        .check(&rules)
+        .unwrap_err()
+        .to_string(),
+        "hands needs 3 distinct symbols, got \"XXZ\""
+    );
+    assert!(Mapping::PUZZLE
+        .check(&Rules::ROCK_PAPER_SCISSORS_LIZARD_SPOCK)
+        .is_err());
+}
+
+#[test]
+fn test_report() {
+    let day = Day2::PUZZLE;
+    let rounds = day.parse(&crate::file::read_example(2)).unwrap();
+    let report = Report::new(&day.rules, 1, &day.games(&rounds, 1).unwrap());
+
+    assert_eq!(report.score(), 15);
+    assert_eq!((report.wins, report.draws, report.losses), (1, 1, 1));
+    assert_eq!((report.shape_score, report.outcome_score), (6, 9));
+    assert_eq!(
+        report.matchups[0],
+        Matchup {
+            other: Hand::ROCK,
+            rounds: 1,
+            score: 8,
+            best: 8,
+            worst: 3,
+        }
+    );
 
-    println!("{}", total_score);
+    let json = report.to_json(&day.rules);
+    assert_eq!(json["score"], 15);
+    assert_eq!(json["opponents"][1]["hand"], "paper");
+    assert_eq!(json["opponents"][1]["worst"], 1);
 }
@@ -1,121 +1,292 @@
-use std::io::Error;
+use crate::{
+    error::{parse_lines, Error, Result},
+    group_by::GroupByTrait,
+    input::Input,
+    solution::Solution,
+};
 
-use crate::file::read_lines;
+const DAY: u8 = 3;
 
-pub fn part_1() {
-    let sum = read_lines("day3.txt")
-        .map(line_to_halves)
-        .map(find_common_char)
-        .map(char_to_priority)
-        .sum::<i32>();
-
-    println!("{:?}", sum);
+/// The priority of an item: 1 to 26 for `a` to `z`, and 27 to 52 for `A` to
+/// `Z`.
+fn priority(item: char) -> Option<i32> {
+    match item {
+        'a'..='z' => Some(item as i32 - 'a' as i32 + 1),
+        'A'..='Z' => Some(item as i32 - 'A' as i32 + 27),
+        _ => None,
+    }
 }
 
-fn char_to_priority(char: Option<char>) -> i32 {
-    let ascii = char.unwrap() as i32;
+/// A set of items, with one bit per item at its priority.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn new(items: &str) -> Result<Self> {
+        items.chars().try_fold(ItemSet::default(), |set, item| {
+            let priority = priority(item)
+                .ok_or_else(|| Error::parse(DAY, "invalid item", &item.to_string()))?;
 
-    match ascii {
-        97..=122 => ascii - 97 + 1,
-        65..=90 => ascii - 65 + 27,
-        _ => panic!("should not happen"),
+            Ok(ItemSet(set.0 | 1 << priority))
+        })
     }
-}
 
-fn line_to_halves(line: Result<String, Error>) -> (String, String) {
-    let line = line.unwrap().clone();
-    let (left, right) = line.split_at(line.len() / 2);
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    /// The items found in every one of `sets`, none if there are no sets.
+    pub fn common<I>(sets: I) -> ItemSet
+    where
+        I: IntoIterator<Item = ItemSet>,
+    {
+        sets.into_iter()
+            .reduce(ItemSet::intersection)
+            .unwrap_or_default()
+    }
 
-    (left.to_string(), right.to_string())
+    /// The priorities of the items in the set, lowest first.
+    pub fn priorities(self) -> impl Iterator<Item = i32> {
+        (1..=52).filter(move |priority| self.0 & 1 << priority != 0)
+    }
+
+    pub fn items(self) -> impl Iterator<Item = char> {
+        self.priorities().map(|priority| match priority {
+            1..=26 => (b'a' + priority as u8 - 1) as char,
+            _ => (b'A' + priority as u8 - 27) as char,
+        })
+    }
 }
 
 #[test]
-fn test_line_to_halves() {
+fn test_item_set() {
+    let left = ItemSet::new("vJrwpWtwJgWr").unwrap();
+    let right = ItemSet::new("hcsFMMfFFhFp").unwrap();
+
+    assert_eq!(left.intersection(right).items().collect::<String>(), "p");
+    assert_eq!(
+        left.intersection(right).priorities().collect::<Vec<_>>(),
+        [16]
+    );
+    assert_eq!(
+        ItemSet::new("abZ")
+            .unwrap()
+            .union(ItemSet::new("bcA").unwrap())
+            .items()
+            .collect::<String>(),
+        "abcAZ"
+    );
+    assert_eq!(
+        ItemSet::common([
+            ItemSet::new("abcd").unwrap(),
+            ItemSet::new("bcde").unwrap(),
+            ItemSet::new("xcbz").unwrap(),
+        ])
+        .items()
+        .collect::<String>(),
+        "bc"
+    );
+    assert_eq!(ItemSet::common([]), ItemSet::default());
     assert_eq!(
-        line_to_halves(Ok("vJrwpWtwJgWrhcsFMMfFFhFp".to_string())),
-        ("vJrwpWtwJgWr".to_string(), "hcsFMMfFFhFp".to_string())
+        ItemSet::new("ab1").unwrap_err().to_string(),
+        "day3.txt: invalid item \"1\""
     );
 }
 
-fn find_common_char((left, right): (String, String)) -> Option<char> {
-    for left_char in left.chars() {
-        if let Some(_) = right.find(|x| x == left_char) {
-            return Some(left_char);
-        }
+/// The items in the two compartments of a rucksack, which each hold one half
+/// of its items.
+fn compartments(line: &str) -> Result<(ItemSet, ItemSet)> {
+    // Every valid item is a single byte, so checking them first makes the
+    // split below land between two items.
+    ItemSet::new(line)?;
+
+    if line.is_empty() {
+        return Err(Error::parse(DAY, "empty rucksack", line));
     }
+    if !line.len().is_multiple_of(2) {
+        return Err(Error::parse(DAY, "odd number of items", line));
+    }
+
+    let (left, right) = line.split_at(line.len() / 2);
 
-    None
+    Ok((ItemSet::new(left)?, ItemSet::new(right)?))
 }
 
 #[test]
-fn test_find_common_char() {
+fn test_compartments() {
     assert_eq!(
-        find_common_char(("hej".to_string(), "fest".to_string())),
-        Some('e')
+        compartments("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap(),
+        (
+            ItemSet::new("vJrwpWtwJgWr").unwrap(),
+            ItemSet::new("hcsFMMfFFhFp").unwrap()
+        )
     );
+    assert!(compartments("abc").is_err());
+    assert!(compartments("aéb").is_err());
 }
 
-struct ThreeLines<I> {
-    iter: I,
+/// The rucksacks are searched for badges in groups of `group_size`.
+pub struct Day3 {
+    pub group_size: usize,
 }
 
-impl<I> Iterator for ThreeLines<I>
-where
-    I: Iterator<Item = Result<String, Error>>,
-{
-    type Item = Vec<String>;
+impl Day3 {
+    pub const PUZZLE: Day3 = Day3 { group_size: 3 };
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let three = self.iter.by_ref().take(3).collect::<Vec<I::Item>>();
+    /// The items of every rucksack, in groups of `group_size`. Only the badge
+    /// search needs the rucksacks to split into whole groups, so an incomplete
+    /// group is reported at its first rucksack, shown by its items.
+    fn badge_groups<'a>(
+        &self,
+        rucksacks: &'a [(ItemSet, ItemSet)],
+    ) -> Result<impl Iterator<Item = Vec<ItemSet>> + 'a> {
+        if self.group_size == 0 {
+            return Err(Error::Usage(
+                "groups need at least one rucksack".to_string(),
+            ));
+        }
 
-        if three.len() == 0 {
-            return None;
+        if !rucksacks.len().is_multiple_of(self.group_size) {
+            let first = rucksacks.len() / self.group_size * self.group_size;
+            let (left, right) = rucksacks[first];
+
+            return Err(Error::parse(
+                DAY,
+                &format!("incomplete group of {} rucksacks", self.group_size),
+                &left.union(right).items().collect::<String>(),
+            )
+            .at_line(first + 1));
         }
 
-        Some(
-            three
-                .into_iter()
-                .map(|x| x.unwrap())
-                .collect::<Vec<String>>(),
-        )
+        Ok(rucksacks
+            .iter()
+            .map(|&(left, right)| left.union(right))
+            .group_by(self.group_size))
     }
 }
 
-trait ThreeLinesTrait<I> {
-    fn three_lines(self) -> ThreeLines<I>;
-}
+impl Solution for Day3 {
+    type Input = Vec<(ItemSet, ItemSet)>;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-impl<I> ThreeLinesTrait<I> for I
-where
-    I: Iterator,
-{
-    fn three_lines(self) -> ThreeLines<I> {
-        ThreeLines { iter: self }
+    fn day(&self) -> u8 {
+        DAY
     }
-}
 
-fn common_char_in_vec(vec: Vec<String>) -> Option<char> {
-    let a = &vec[0];
-    let b = &vec[1];
-    let c = &vec[2];
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        parse_lines(input.as_str(), compartments)
+    }
 
-    for char in a.chars() {
-        if let Some(_) = b.find(|c| c == char) {
-            if let Some(_) = c.find(|c| c == char) {
-                return Some(char);
-            }
-        }
+    fn part_1(&self, rucksacks: &Self::Input) -> Result<Self::Answer1> {
+        Ok(rucksacks
+            .iter()
+            .flat_map(|&(left, right)| left.intersection(right).priorities())
+            .sum())
     }
 
-    None
+    fn part_2(&self, rucksacks: &Self::Input) -> Result<Self::Answer2> {
+        Ok(self
+            .badge_groups(rucksacks)?
+            .flat_map(|group| ItemSet::common(group).priorities())
+            .sum())
+    }
 }
 
-pub fn part_2() {
-    let sum = read_lines("day3.txt")
-        .three_lines()
-        .map(common_char_in_vec)
-        .map(char_to_priority)
-        .sum::<i32>();
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_1() {
+    let day = Day3::PUZZLE;
 
-    println!("{:?}", sum);
+    assert_eq!(
+        day.part_1(&day.parse(&crate::file::read_input(3)).unwrap())
+            .unwrap(),
+        8176
+    );
+}
+
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_2() {
+    let day = Day3::PUZZLE;
+
+    assert_eq!(
+        day.part_2(&day.parse(&crate::file::read_input(3)).unwrap())
+            .unwrap(),
+        2689
+    );
+}
+
+#[test]
+fn test_example_part_1() {
+    let day = Day3::PUZZLE;
+
+    assert_eq!(
+        day.part_1(&day.parse(&crate::file::read_example(3)).unwrap())
+            .unwrap(),
+        157
+    );
+}
+
+#[test]
+fn test_example_part_2() {
+    let day = Day3::PUZZLE;
+
+    assert_eq!(
+        day.part_2(&day.parse(&crate::file::read_example(3)).unwrap())
+            .unwrap(),
+        70
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Day3::PUZZLE
+            .parse(&"abab\nab-b".into())
+            .unwrap_err()
+            .to_string(),
+        "day3.txt:2: invalid item \"-\""
+    );
+    assert_eq!(
+        Day3::PUZZLE
+            .parse(&"abab\n\ncdcd".into())
+            .unwrap_err()
+            .to_string(),
+        "day3.txt:2: empty rucksack \"\""
+    );
+}
+
+#[test]
+fn test_group_size() {
+    let input = crate::file::read_example(3);
+
+    let day = Day3::PUZZLE;
+    let rucksacks = day.parse(&"ab\ncd\nef\nhg".into()).unwrap();
+    assert!(day.part_1(&rucksacks).is_ok());
+    assert_eq!(
+        day.part_2(&rucksacks).unwrap_err().to_string(),
+        "day3.txt:4: incomplete group of 3 rucksacks \"gh\""
+    );
+
+    let day = Day3 { group_size: 2 };
+    assert!(day.parse(&input).is_ok());
+    assert_eq!(
+        day.part_2(&day.parse(&"aB\nBc\nxy\nzx".into()).unwrap())
+            .unwrap(),
+        28 + 24
+    );
+
+    let day = Day3 { group_size: 0 };
+    assert!(day.part_2(&day.parse(&input).unwrap()).is_err());
+
+    let day = Day3 { group_size: 1 };
+    assert_eq!(
+        day.part_2(&day.parse(&"ab\nab".into()).unwrap()).unwrap(),
+        6
+    );
 }
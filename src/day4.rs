@@ -1,22 +1,48 @@
-use std::{io::Error, ops::RangeInclusive};
-
-use crate::file::read_lines;
-
-fn line_to_ranges(line: Result<String, Error>) -> Vec<RangeInclusive<i32>> {
-    let groups: Vec<String> = line.unwrap().split(',').map(|x| x.to_string()).collect();
-    groups
-        .iter()
-        .map(|group| {
-            group
-                .split('-')
-                .map(|x| x.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
-        })
-        .map(|x| x[0]..=x[1])
-        .collect::<Vec<RangeInclusive<i32>>>()
+use std::ops::RangeInclusive;
+
+use crate::{
+    error::{parse_lines, parse_number, Error, Result},
+    input::Input,
+    solution::Solution,
+};
+
+const DAY: u8 = 4;
+
+fn group_to_range(group: &str) -> Result<RangeInclusive<i32>> {
+    let (start, end) = group
+        .split_once('-')
+        .ok_or_else(|| Error::parse(DAY, "invalid range", group))?;
+
+    Ok(parse_number(DAY, start)?..=parse_number(DAY, end)?)
+}
+
+fn line_to_ranges(line: &str) -> Result<Vec<RangeInclusive<i32>>> {
+    let ranges = line
+        .split(',')
+        .map(group_to_range)
+        .collect::<Result<Vec<RangeInclusive<i32>>>>()?;
+
+    if ranges.len() != 2 {
+        return Err(Error::parse(DAY, "expected a pair of ranges", line));
+    }
+
+    Ok(ranges)
 }
 
-fn ranges_fully_contained(ranges: &Vec<RangeInclusive<i32>>) -> bool {
+#[test]
+fn test_line_to_ranges() {
+    assert_eq!(line_to_ranges("2-4,6-8").unwrap(), vec![2..=4, 6..=8]);
+    assert_eq!(
+        line_to_ranges("2-4,6").unwrap_err().to_string(),
+        "day4.txt: invalid range \"6\""
+    );
+    assert_eq!(
+        line_to_ranges("2-4").unwrap_err().to_string(),
+        "day4.txt: expected a pair of ranges \"2-4\""
+    );
+}
+
+fn ranges_fully_contained(ranges: &[RangeInclusive<i32>]) -> bool {
     let range1 = &ranges[0];
     let range2 = &ranges[1];
 
@@ -34,21 +60,7 @@ fn ranges_fully_contained(ranges: &Vec<RangeInclusive<i32>>) -> bool {
     }
 }
 
-pub fn part_1() {
-    let pairs = read_lines("day4.txt")
-        .map(line_to_ranges)
-        .filter(ranges_fully_contained)
-        .count();
-
-    println!("{:?}", pairs);
-}
-
-#[test]
-fn test_part_1() {
-    part_1()
-}
-
-fn ranges_overlap(ranges: &Vec<RangeInclusive<i32>>) -> bool {
+fn ranges_overlap(ranges: &[RangeInclusive<i32>]) -> bool {
     let range1 = &ranges[0];
     let range2 = ranges[1].clone();
 
@@ -61,16 +73,67 @@ fn ranges_overlap(ranges: &Vec<RangeInclusive<i32>>) -> bool {
     false
 }
 
-pub fn part_2() {
-    let pairs = read_lines("day4.txt")
-        .map(line_to_ranges)
-        .filter(ranges_overlap)
-        .count();
+pub struct Day4;
 
-    println!("{:?}", pairs);
+impl Solution for Day4 {
+    type Input = Vec<Vec<RangeInclusive<i32>>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        parse_lines(input.as_str(), line_to_ranges)
+    }
+
+    fn part_1(&self, pairs: &Self::Input) -> Result<Self::Answer1> {
+        Ok(pairs
+            .iter()
+            .filter(|ranges| ranges_fully_contained(ranges))
+            .count())
+    }
+
+    fn part_2(&self, pairs: &Self::Input) -> Result<Self::Answer2> {
+        Ok(pairs.iter().filter(|ranges| ranges_overlap(ranges)).count())
+    }
+}
+
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_1() {
+    assert_eq!(
+        Day4.part_1(&Day4.parse(&crate::file::read_input(4)).unwrap())
+            .unwrap(),
+        556
+    );
 }
 
 #[test]
+#[ignore = "reads the full puzzle input"]
 fn test_part_2() {
-    part_2()
+    assert_eq!(
+        Day4.part_2(&Day4.parse(&crate::file::read_input(4)).unwrap())
+            .unwrap(),
+        876
+    );
+}
+
+#[test]
+fn test_example_part_1() {
+    assert_eq!(
+        Day4.part_1(&Day4.parse(&crate::file::read_example(4)).unwrap())
+            .unwrap(),
+        2
+    );
+}
+
+#[test]
+fn test_example_part_2() {
+    assert_eq!(
+        Day4.part_2(&Day4.parse(&crate::file::read_example(4)).unwrap())
+            .unwrap(),
+        4
+    );
 }
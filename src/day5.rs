@@ -1,141 +1,215 @@
 use regex::Regex;
 
-use crate::{file::read_lines, group_by::GroupByTrait};
-
-pub fn part_1() {
-    let mut lines = read_lines("day5.txt");
-
-    let mut crates: Vec<_> = lines
-        .by_ref()
-        .map(|line| line.unwrap())
-        .take_while(|line| line != "")
-        .map(|line| {
-            line.chars()
-                .group_by(4)
-                .map(|x| x[1])
-                .collect::<Vec<char>>()
-        })
-        .fold(vec![], |acc, val| {
-            let mut acc = acc;
-
-            for (index, val) in val.into_iter().enumerate() {
-                if val != ' ' && val as u8 > 57 {
-                    while acc.len() <= index {
-                        acc.push(vec![])
-                    }
+use crate::{
+    error::{parse_number, Error, Result},
+    group_by::GroupByTrait,
+    input::Input,
+    solution::Solution,
+};
 
-                    acc[index].push(val)
-                }
-            }
-            acc
-        })
-        .into_iter()
-        .map(|x| x.into_iter().rev().collect::<Vec<char>>())
-        .collect();
+const DAY: u8 = 5;
 
-    let regex = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
+type Crates = Vec<Vec<char>>;
+type Procedure = Vec<(usize, usize, usize)>;
 
-    let procedure: Vec<_> = lines
-        .map(|line| line.unwrap())
-        .map(|line| {
-            let caps = regex.captures(&line).unwrap();
-
-            (
-                caps.get(1).unwrap().as_str().parse::<usize>().unwrap(),
-                caps.get(2).unwrap().as_str().parse::<usize>().unwrap() - 1,
-                caps.get(3).unwrap().as_str().parse::<usize>().unwrap() - 1,
-            )
-        })
-        .collect();
-
-    for (n, from, to) in procedure {
-        for _ in 0..n {
-            let element = crates[from].pop().unwrap();
-            crates[to].push(element);
-        }
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (Crates, Procedure);
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn day(&self) -> u8 {
+        DAY
     }
 
-    let top_of_stack = crates
-        .iter()
-        .map(|x| x.last().unwrap_or(&' '))
-        .collect::<String>();
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        let mut paragraphs = input.paragraphs();
 
-    println!("{:?}", top_of_stack.trim());
-}
+        let drawing = paragraphs.next().unwrap_or_default();
+        let moves = paragraphs.next().unwrap_or_default();
 
-#[test]
-fn test_part1() {
-    part_1();
-}
+        if let Some(paragraph) = paragraphs.next() {
+            let (line, number) = paragraph[0];
+            return Err(Error::parse(DAY, "unexpected paragraph", line).at_line(number));
+        }
 
-pub fn part_2() {
-    let mut lines = read_lines("day5.txt");
-
-    let mut crates: Vec<_> = lines
-        .by_ref()
-        .map(|line| line.unwrap())
-        .take_while(|line| line != "")
-        .map(|line| {
-            line.chars()
-                .group_by(4)
-                .map(|x| x[1])
-                .collect::<Vec<char>>()
-        })
-        .fold(vec![], |acc, val| {
-            let mut acc = acc;
-
-            for (index, val) in val.into_iter().enumerate() {
-                if val != ' ' && val as u8 > 57 {
-                    while acc.len() <= index {
-                        acc.push(vec![])
+        let crates: Vec<_> = drawing
+            .into_iter()
+            .map(|(line, _)| {
+                line.chars()
+                    .group_by(4)
+                    .map(|x| x.get(1).copied().unwrap_or(' '))
+                    .collect::<Vec<char>>()
+            })
+            .fold(vec![], |acc, val| {
+                let mut acc = acc;
+
+                for (index, val) in val.into_iter().enumerate() {
+                    if val != ' ' && val as u8 > 57 {
+                        while acc.len() <= index {
+                            acc.push(vec![])
+                        }
+
+                        acc[index].push(val)
                     }
-
-                    acc[index].push(val)
                 }
-            }
-            acc
-        })
-        .into_iter()
-        .map(|x| x.into_iter().rev().collect::<Vec<char>>())
-        .collect();
+                acc
+            })
+            .into_iter()
+            .map(|x| x.into_iter().rev().collect::<Vec<char>>())
+            .collect();
+
+        let regex = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
+
+        let procedure = moves
+            .into_iter()
+            .map(|(line, number)| {
+                parse_move(&regex, line, crates.len()).map_err(|error| error.at_line(number))
+            })
+            .collect::<Result<Procedure>>()?;
+
+        Ok((crates, procedure))
+    }
 
-    let regex = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
+    fn part_1(&self, (crates, procedure): &Self::Input) -> Result<Self::Answer1> {
+        let mut crates = crates.clone();
 
-    let procedure: Vec<_> = lines
-        .map(|line| line.unwrap())
-        .map(|line| {
-            let caps = regex.captures(&line).unwrap();
-
-            (
-                caps.get(1).unwrap().as_str().parse::<usize>().unwrap(),
-                caps.get(2).unwrap().as_str().parse::<usize>().unwrap() - 1,
-                caps.get(3).unwrap().as_str().parse::<usize>().unwrap() - 1,
-            )
-        })
-        .collect();
-
-    for (n, from, to) in procedure {
-        let mut vec = vec![];
-        for _ in 0..n {
-            vec.push(crates[from].pop().unwrap());
+        for &(n, from, to) in procedure {
+            for _ in 0..n {
+                let element = crates[from].pop().ok_or_else(empty_stack)?;
+                crates[to].push(element);
+            }
         }
 
-        vec.reverse();
+        Ok(top_of_stack(&crates))
+    }
 
-        for c in vec {
-            crates[to].push(c);
+    fn part_2(&self, (crates, procedure): &Self::Input) -> Result<Self::Answer2> {
+        let mut crates = crates.clone();
+
+        for &(n, from, to) in procedure {
+            let mut vec = vec![];
+            for _ in 0..n {
+                vec.push(crates[from].pop().ok_or_else(empty_stack)?);
+            }
+
+            vec.reverse();
+
+            for c in vec {
+                crates[to].push(c);
+            }
         }
+
+        Ok(top_of_stack(&crates))
     }
+}
+
+fn parse_move(regex: &Regex, line: &str, stacks: usize) -> Result<(usize, usize, usize)> {
+    let caps = regex
+        .captures(line)
+        .ok_or_else(|| Error::parse(DAY, "invalid move", line))?;
+
+    let stack = |index: usize| -> Result<usize> {
+        let text = caps.get(index).unwrap().as_str();
+        match parse_number::<usize>(DAY, text)? {
+            stack @ 1.. if stack <= stacks => Ok(stack - 1),
+            _ => Err(Error::parse(DAY, "unknown stack", text)),
+        }
+    };
 
-    let top_of_stack = crates
+    Ok((
+        parse_number(DAY, caps.get(1).unwrap().as_str())?,
+        stack(2)?,
+        stack(3)?,
+    ))
+}
+
+#[test]
+fn test_parse_move() {
+    let regex = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
+
+    assert_eq!(
+        parse_move(&regex, "move 3 from 1 to 2", 2).unwrap(),
+        (3, 0, 1)
+    );
+    assert_eq!(
+        parse_move(&regex, "move 3 from 1 to 4", 2)
+            .unwrap_err()
+            .to_string(),
+        "day5.txt: unknown stack \"4\""
+    );
+    assert_eq!(
+        parse_move(&regex, "lift 3 from 1 to 2", 2)
+            .unwrap_err()
+            .to_string(),
+        "day5.txt: invalid move \"lift 3 from 1 to 2\""
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Day5.parse(&"[A]\n 1\n\nmove 1 from 1 to 1\n\nmove 1 from 1 to 1".into())
+            .unwrap_err()
+            .to_string(),
+        "day5.txt:6: unexpected paragraph \"move 1 from 1 to 1\""
+    );
+    assert_eq!(
+        Day5.parse(&"[A]\n 1\n\nmove 1 from 1 to 2".into())
+            .unwrap_err()
+            .to_string(),
+        "day5.txt:4: unknown stack \"2\""
+    );
+}
+
+fn empty_stack() -> Error {
+    Error::no_answer(DAY, "a crate was moved from an empty stack")
+}
+
+fn top_of_stack(crates: &[Vec<char>]) -> String {
+    crates
         .iter()
         .map(|x| x.last().unwrap_or(&' '))
-        .collect::<String>();
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
 
-    println!("{:?}", top_of_stack.trim());
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part1() {
+    assert_eq!(
+        Day5.part_1(&Day5.parse(&crate::file::read_input(5)).unwrap())
+            .unwrap(),
+        "SHQWSRBDL"
+    );
 }
 
 #[test]
+#[ignore = "reads the full puzzle input"]
 fn test_part2() {
-    part_2();
+    assert_eq!(
+        Day5.part_2(&Day5.parse(&crate::file::read_input(5)).unwrap())
+            .unwrap(),
+        "CDTQZHBRS"
+    );
+}
+
+#[test]
+fn test_example_part_1() {
+    assert_eq!(
+        Day5.part_1(&Day5.parse(&crate::file::read_example(5)).unwrap())
+            .unwrap(),
+        "CMZ"
+    );
+}
+
+#[test]
+fn test_example_part_2() {
+    assert_eq!(
+        Day5.part_2(&Day5.parse(&crate::file::read_example(5)).unwrap())
+            .unwrap(),
+        "MCD"
+    );
 }
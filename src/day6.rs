@@ -1,6 +1,16 @@
 use std::collections::HashSet;
 
-use crate::file::read_lines;
+use crate::{
+    error::{Error, Result},
+    input::Input,
+    solution::Solution,
+};
+
+const DAY: u8 = 6;
+
+fn no_marker() -> Error {
+    Error::no_answer(DAY, "no marker found")
+}
 
 fn find_marker(n: usize, bytes: &[u8]) -> Option<usize> {
     for (index, window) in bytes.windows(n).enumerate() {
@@ -17,32 +27,74 @@ fn find_marker(n: usize, bytes: &[u8]) -> Option<usize> {
     None
 }
 
-pub fn part_1() {
-    let lines = read_lines("day6.txt").map(|x| x.unwrap());
+pub struct Day6;
 
-    for line in lines {
-        if let Some(marker) = find_marker(4, &line.as_bytes()) {
-            println!("{:?}", marker);
-        }
+impl Solution for Day6 {
+    type Input = Vec<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        Ok(input.bytes().to_vec())
+    }
+
+    fn part_1(&self, datastream: &Self::Input) -> Result<Self::Answer1> {
+        find_marker(4, datastream).ok_or_else(no_marker)
+    }
+
+    fn part_2(&self, datastream: &Self::Input) -> Result<Self::Answer2> {
+        find_marker(14, datastream).ok_or_else(no_marker)
     }
 }
 
 #[test]
+#[ignore = "reads the full puzzle input"]
 fn test_part_1() {
-    part_1();
+    assert_eq!(
+        Day6.part_1(&Day6.parse(&crate::file::read_input(6)).unwrap())
+            .unwrap(),
+        1965
+    );
 }
 
-pub fn part_2() {
-    let lines = read_lines("day6.txt").map(|x| x.unwrap());
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_2() {
+    assert_eq!(
+        Day6.part_2(&Day6.parse(&crate::file::read_input(6)).unwrap())
+            .unwrap(),
+        2773
+    );
+}
 
-    for line in lines {
-        if let Some(marker) = find_marker(14, &line.as_bytes()) {
-            println!("{:?}", marker);
-        }
-    }
+#[test]
+fn test_example_part_1() {
+    assert_eq!(
+        Day6.part_1(&Day6.parse(&crate::file::read_example(6)).unwrap())
+            .unwrap(),
+        7
+    );
 }
 
 #[test]
-fn test_part_2() {
-    part_2();
+fn test_example_part_2() {
+    assert_eq!(
+        Day6.part_2(&Day6.parse(&crate::file::read_example(6)).unwrap())
+            .unwrap(),
+        19
+    );
+}
+
+#[test]
+fn test_no_marker() {
+    let datastream = Day6.parse(&"aaaa".into()).unwrap();
+
+    assert_eq!(
+        Day6.part_1(&datastream).unwrap_err().to_string(),
+        "day6.txt: no marker found"
+    );
 }
@@ -1,18 +1,26 @@
 use indextree::{Arena, NodeId};
 
-use crate::file::read_lines;
+use crate::{
+    error::{parse_number, Error, Result},
+    input::Input,
+    solution::Solution,
+};
 
-fn get_commands<I>(iter: I) -> Vec<(String, Vec<String>)>
+const DAY: u8 = 7;
+
+type Line = (usize, String);
+
+fn get_commands<I>(iter: I) -> Vec<(Line, Vec<Line>)>
 where
-    I: Iterator<Item = String>,
+    I: Iterator<Item = Line>,
 {
     let mut commands = vec![];
-    let mut command: String = "".into();
+    let mut command: Line = (0, "".into());
     let mut result = vec![];
 
     for line in iter {
-        if line.starts_with('$') {
-            if !command.is_empty() {
+        if line.1.starts_with('$') {
+            if !command.1.is_empty() {
                 commands.push((command, result));
                 result = vec![];
             }
@@ -22,7 +30,7 @@ where
         }
     }
 
-    if result.len() > 0 {
+    if !command.1.is_empty() || !result.is_empty() {
         commands.push((command, result));
     }
 
@@ -35,71 +43,62 @@ enum FSItem {
     File(String, usize),
 }
 
-fn to_filesystem(arena: &mut Arena<FSItem>, commands: &Vec<(String, Vec<String>)>) -> NodeId {
+fn to_filesystem(arena: &mut Arena<FSItem>, commands: &[(Line, Vec<Line>)]) -> Result<NodeId> {
     let root = arena.new_node(FSItem::Dir("/".to_string()));
 
     let mut node = root.ancestors(arena).next().unwrap();
 
-    for (command, result) in commands {
-        let command: Vec<_> = command.split_whitespace().take(3).collect();
-        match command[1] {
-            "cd" => {
-                if command[2] == ".." {
-                    node = arena.get(node).unwrap().parent().unwrap();
-                } else if command[2] == "/" {
-                } else {
-                    let dir = node
-                        .children(arena)
-                        .find(|x| {
-                            *arena.get(*x).unwrap().get() == FSItem::Dir(command[2].to_string())
-                        })
-                        .unwrap();
-
-                    node = dir;
-                }
+    for ((number, command), result) in commands {
+        let at_line = |error: Error| error.at_line(*number);
+
+        match command.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["$", "cd", ".."] => {
+                node =
+                    arena.get(node).unwrap().parent().ok_or_else(|| {
+                        at_line(Error::parse(DAY, "no parent directory", command))
+                    })?;
             }
-            "ls" => {
-                for line in result
-                    .iter()
-                    .map(|line| line.split_whitespace().collect::<Vec<_>>())
-                {
-                    if line[0] == "dir" {
-                        node.append(arena.new_node(FSItem::Dir(line[1].to_string())), arena);
-                    } else {
-                        node.append(
-                            arena.new_node(FSItem::File(
-                                line[1].to_string(),
-                                line[0].parse().unwrap(),
-                            )),
-                            arena,
-                        );
+            ["$", "cd", "/"] => {}
+            ["$", "cd", name] => {
+                let dir = node
+                    .children(arena)
+                    .find(|x| *arena.get(*x).unwrap().get() == FSItem::Dir(name.to_string()))
+                    .ok_or_else(|| at_line(Error::parse(DAY, "unknown directory", name)))?;
+
+                node = dir;
+            }
+            ["$", "ls"] => {
+                for (number, line) in result {
+                    let at_line = |error: Error| error.at_line(*number);
+
+                    match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                        ["dir", name] => {
+                            node.append(arena.new_node(FSItem::Dir(name.to_string())), arena);
+                        }
+                        [size, name] => {
+                            let size = parse_number(DAY, size).map_err(at_line)?;
+                            node.append(
+                                arena.new_node(FSItem::File(name.to_string(), size)),
+                                arena,
+                            );
+                        }
+                        _ => return Err(at_line(Error::parse(DAY, "invalid listing", line))),
                     }
                 }
             }
-            _ => panic!("Unsupported command"),
+            _ => return Err(at_line(Error::parse(DAY, "unsupported command", command))),
         }
     }
 
-    root
+    Ok(root)
 }
 
-pub fn part_1() {
-    let lines = read_lines("day7.txt").map(|x| x.unwrap());
-    let commands = get_commands(lines);
-    let mut arena = Arena::new();
-    let filesystem = to_filesystem(&mut arena, &commands);
-
-    let sum = filesystem
-        .descendants(&arena)
-        .filter(|x| {
-            if let FSItem::Dir(_) = arena.get(*x).unwrap().get() {
-                true
-            } else {
-                false
-            }
-        })
+fn dir_sizes(arena: &Arena<FSItem>, filesystem: NodeId) -> Vec<usize> {
+    filesystem
+        .descendants(arena)
+        .filter(|x| matches!(arena.get(*x).unwrap().get(), FSItem::Dir(_)))
         .map(|node| {
-            node.descendants(&arena)
+            node.descendants(arena)
                 .map(|inner_node| {
                     if let FSItem::File(_, size) = arena.get(inner_node).unwrap().get() {
                         size
@@ -109,55 +108,115 @@ pub fn part_1() {
                 })
                 .sum::<usize>()
         })
-        .filter(|x| *x <= 100000)
-        .sum::<usize>();
+        .collect()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    println!("{:?}", sum)
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        let commands = get_commands((1..).zip(input.lines().map(|line| line.to_string())));
+        let mut arena = Arena::new();
+        let filesystem = to_filesystem(&mut arena, &commands)?;
+
+        Ok(dir_sizes(&arena, filesystem))
+    }
+
+    fn part_1(&self, dirs: &Self::Input) -> Result<Self::Answer1> {
+        Ok(dirs.iter().filter(|x| **x <= 100000).sum::<usize>())
+    }
+
+    fn part_2(&self, dirs: &Self::Input) -> Result<Self::Answer2> {
+        let no_directories = || Error::no_answer(DAY, "no directories");
+
+        let root_size = dirs.iter().max().ok_or_else(no_directories)?;
+        let unused = 70000000usize.saturating_sub(*root_size);
+        let minimum_freed = 30000000usize.saturating_sub(unused);
+
+        dirs.iter()
+            .filter(|x| **x >= minimum_freed)
+            .min()
+            .copied()
+            .ok_or_else(no_directories)
+    }
 }
 
 #[test]
+#[ignore = "reads the full puzzle input"]
 fn test_part_1() {
-    part_1()
+    assert_eq!(
+        Day7.part_1(&Day7.parse(&crate::file::read_input(7)).unwrap())
+            .unwrap(),
+        1477771
+    );
 }
 
-pub fn part_2() {
-    let lines = read_lines("day7.txt").map(|x| x.unwrap());
-    let commands = get_commands(lines);
-    let mut arena = Arena::new();
-    let filesystem = to_filesystem(&mut arena, &commands);
-
-    let dirs: Vec<_> = filesystem
-        .descendants(&arena)
-        .filter(|x| {
-            if let FSItem::Dir(_) = arena.get(*x).unwrap().get() {
-                true
-            } else {
-                false
-            }
-        })
-        .map(|node| {
-            node.descendants(&arena)
-                .map(|inner_node| {
-                    if let FSItem::File(_, size) = arena.get(inner_node).unwrap().get() {
-                        size
-                    } else {
-                        &0
-                    }
-                })
-                .sum::<usize>()
-        })
-        .collect();
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_2() {
+    assert_eq!(
+        Day7.part_2(&Day7.parse(&crate::file::read_input(7)).unwrap())
+            .unwrap(),
+        3579501
+    );
+}
 
-    let root_size = dirs.iter().max().unwrap();
-    let unused = 70000000 - root_size;
-    let minimum_freed = 30000000 - unused;
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Day7.parse(&"$ cd /\n$ cd a".into())
+            .unwrap_err()
+            .to_string(),
+        "day7.txt:2: unknown directory \"a\""
+    );
+    assert_eq!(
+        Day7.parse(&"$ ls\nbig a.txt".into())
+            .unwrap_err()
+            .to_string(),
+        "day7.txt:2: invalid number \"big\""
+    );
+    assert_eq!(
+        Day7.parse(&"$ rm -rf /\n".into()).unwrap_err().to_string(),
+        "day7.txt:1: unsupported command \"$ rm -rf /\""
+    );
+}
 
-    let dir_size_to_remove = dirs.iter().filter(|x| **x >= minimum_freed).min().unwrap();
+#[test]
+fn test_small_filesystems() {
+    assert_eq!(
+        Day7.part_2(&Day7.parse(&"$ cd /\n$ ls\n100 a".into()).unwrap())
+            .unwrap(),
+        100
+    );
+    assert_eq!(Day7.part_2(&Day7.parse(&"".into()).unwrap()).unwrap(), 0);
+    assert_eq!(
+        Day7.part_2(&vec![]).unwrap_err().to_string(),
+        "day7.txt: no directories"
+    );
+}
 
-    println!("{:?}", dir_size_to_remove);
+#[test]
+fn test_example_part_1() {
+    assert_eq!(
+        Day7.part_1(&Day7.parse(&crate::file::read_example(7)).unwrap())
+            .unwrap(),
+        95437
+    );
 }
 
 #[test]
-fn test_part_2() {
-    part_2()
+fn test_example_part_2() {
+    assert_eq!(
+        Day7.part_2(&Day7.parse(&crate::file::read_example(7)).unwrap())
+            .unwrap(),
+        24933642
+    );
 }
@@ -1,145 +1,228 @@
 use itertools::FoldWhile::{Continue, Done};
 use itertools::Itertools;
 
-use crate::file::read_lines;
-
-pub fn part_1() {
-    let input: Vec<_> = read_lines("day8.txt")
-        .map(|line| {
-            line.unwrap()
-                .chars()
-                .map(|c| c.to_digit(10).unwrap() as i32)
-                .collect::<Vec<i32>>()
-        })
-        .flatten()
-        .collect();
-
-    let length = input.len() as f32;
-
-    let size: (usize, usize) = (length.sqrt() as usize, length.sqrt() as usize);
-
-    let mut count = (size.0 * size.1) - ((size.0 - 2) * (size.1 - 2));
-
-    for y in 1..(size.1 - 1) {
-        for x in 1..(size.0 - 1) {
-            let index = y * size.0 + x;
-            let height = input[index];
-
-            let left = (0..x)
-                .map(|x| y * size.0 + x)
-                .map(|index| input[index])
-                .max()
-                .unwrap();
-            let right = ((x + 1)..size.0)
-                .map(|x| y * size.0 + x)
-                .map(|index| input[index])
-                .max()
-                .unwrap();
-            let top = (0..y)
-                .map(|y| y * size.0 + x)
-                .map(|index| input[index])
-                .max()
-                .unwrap();
-            let bottom = ((y + 1)..size.1)
-                .map(|y| y * size.0 + x)
-                .map(|index| input[index])
-                .max()
-                .unwrap();
-
-            if left < height || right < height || top < height || bottom < height {
-                count += 1;
+use crate::{
+    error::{parse_lines, Error, Result},
+    input::Input,
+    solution::Solution,
+};
+
+const DAY: u8 = 8;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<i32>;
+    type Answer1 = usize;
+    type Answer2 = i32;
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        let rows = parse_lines(input.as_str(), |line| {
+            line.chars()
+                .map(|c| {
+                    c.to_digit(10)
+                        .map(|height| height as i32)
+                        .ok_or_else(|| Error::parse(DAY, "invalid tree height", &c.to_string()))
+                })
+                .collect::<Result<Vec<i32>>>()
+        })?;
+
+        if rows.is_empty() {
+            return Err(Error::parse(DAY, "empty forest", ""));
+        }
+
+        if let Some((index, _)) = rows.iter().find_position(|row| row.len() != rows.len()) {
+            return Err(Error::parse(
+                DAY,
+                "the forest is not square",
+                input.lines().nth(index).unwrap(),
+            )
+            .at_line(index + 1));
+        }
+
+        Ok(rows.into_iter().flatten().collect())
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<Self::Answer1> {
+        let length = input.len() as f32;
+
+        let size: (usize, usize) = (length.sqrt() as usize, length.sqrt() as usize);
+
+        // Without an interior, every tree is on the edge.
+        if size.0 < 3 || size.1 < 3 {
+            return Ok(size.0 * size.1);
+        }
+
+        let mut count = (size.0 * size.1) - ((size.0 - 2) * (size.1 - 2));
+
+        for y in 1..(size.1 - 1) {
+            for x in 1..(size.0 - 1) {
+                let index = y * size.0 + x;
+                let height = input[index];
+
+                let left = (0..x)
+                    .map(|x| y * size.0 + x)
+                    .map(|index| input[index])
+                    .max()
+                    .unwrap();
+                let right = ((x + 1)..size.0)
+                    .map(|x| y * size.0 + x)
+                    .map(|index| input[index])
+                    .max()
+                    .unwrap();
+                let top = (0..y)
+                    .map(|y| y * size.0 + x)
+                    .map(|index| input[index])
+                    .max()
+                    .unwrap();
+                let bottom = ((y + 1)..size.1)
+                    .map(|y| y * size.0 + x)
+                    .map(|index| input[index])
+                    .max()
+                    .unwrap();
+
+                if left < height || right < height || top < height || bottom < height {
+                    count += 1;
+                }
             }
         }
+
+        Ok(count)
     }
 
-    println!("{:?}", count);
+    fn part_2(&self, input: &Self::Input) -> Result<Self::Answer2> {
+        let length = input.len() as f32;
+
+        let size: (usize, usize) = (length.sqrt() as usize, length.sqrt() as usize);
+
+        let mut score = vec![];
+
+        for y in 0..size.1 {
+            for x in 0..size.0 {
+                let index = y * size.0 + x;
+                let height = input[index];
+
+                let left = (0..x)
+                    .rev()
+                    .map(|x| y * size.0 + x)
+                    .map(|index| input[index])
+                    .fold_while(0, |acc, tree| {
+                        if tree < height {
+                            Continue(acc + 1)
+                        } else {
+                            Done(acc + 1)
+                        }
+                    })
+                    .into_inner();
+                let right = ((x + 1)..size.0)
+                    .map(|x| y * size.0 + x)
+                    .map(|index| input[index])
+                    .fold_while(0, |acc, tree| {
+                        if tree < height {
+                            Continue(acc + 1)
+                        } else {
+                            Done(acc + 1)
+                        }
+                    })
+                    .into_inner();
+                let top = (0..y)
+                    .rev()
+                    .map(|y| y * size.0 + x)
+                    .map(|index| input[index])
+                    .fold_while(0, |acc, tree| {
+                        if tree < height {
+                            Continue(acc + 1)
+                        } else {
+                            Done(acc + 1)
+                        }
+                    })
+                    .into_inner();
+                let bottom = ((y + 1)..size.1)
+                    .map(|y| y * size.0 + x)
+                    .map(|index| input[index])
+                    .fold_while(0, |acc, tree| {
+                        if tree < height {
+                            Continue(acc + 1)
+                        } else {
+                            Done(acc + 1)
+                        }
+                    })
+                    .into_inner();
+
+                let scenic_score = left * right * top * bottom;
+
+                score.push(scenic_score);
+            }
+        }
+
+        Ok(*score.iter().max().unwrap())
+    }
 }
 
 #[test]
+#[ignore = "reads the full puzzle input"]
 fn test_part_1() {
-    part_1()
+    assert_eq!(
+        Day8.part_1(&Day8.parse(&crate::file::read_input(8)).unwrap())
+            .unwrap(),
+        1705
+    );
 }
 
-pub fn part_2() {
-    let input: Vec<_> = read_lines("day8.txt")
-        .map(|line| {
-            line.unwrap()
-                .chars()
-                .map(|c| c.to_digit(10).unwrap() as i32)
-                .collect::<Vec<i32>>()
-        })
-        .flatten()
-        .collect();
-
-    let length = input.len() as f32;
-
-    let size: (usize, usize) = (length.sqrt() as usize, length.sqrt() as usize);
-
-    let mut score = vec![];
-
-    for y in 0..size.1 {
-        for x in 0..size.0 {
-            let index = y * size.0 + x;
-            let height = input[index];
-
-            let left: _ = (0..x)
-                .rev()
-                .map(|x| y * size.0 + x)
-                .map(|index| input[index])
-                .fold_while(0, |acc, tree| {
-                    if tree < height {
-                        Continue(acc + 1)
-                    } else {
-                        Done(acc + 1)
-                    }
-                })
-                .into_inner();
-            let right: _ = ((x + 1)..size.0)
-                .map(|x| y * size.0 + x)
-                .map(|index| input[index])
-                .fold_while(0, |acc, tree| {
-                    if tree < height {
-                        Continue(acc + 1)
-                    } else {
-                        Done(acc + 1)
-                    }
-                })
-                .into_inner();
-            let top: _ = (0..y)
-                .rev()
-                .map(|y| y * size.0 + x)
-                .map(|index| input[index])
-                .fold_while(0, |acc, tree| {
-                    if tree < height {
-                        Continue(acc + 1)
-                    } else {
-                        Done(acc + 1)
-                    }
-                })
-                .into_inner();
-            let bottom: _ = ((y + 1)..size.1)
-                .map(|y| y * size.0 + x)
-                .map(|index| input[index])
-                .fold_while(0, |acc, tree| {
-                    if tree < height {
-                        Continue(acc + 1)
-                    } else {
-                        Done(acc + 1)
-                    }
-                })
-                .into_inner();
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_2() {
+    assert_eq!(
+        Day8.part_2(&Day8.parse(&crate::file::read_input(8)).unwrap())
+            .unwrap(),
+        371200
+    );
+}
 
-            let scenic_score = left * right * top * bottom;
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Day8.parse(&"12\n3x".into()).unwrap_err().to_string(),
+        "day8.txt:2: invalid tree height \"x\""
+    );
+    assert_eq!(
+        Day8.parse(&"12\n345".into()).unwrap_err().to_string(),
+        "day8.txt:2: the forest is not square \"345\""
+    );
+    assert_eq!(
+        Day8.parse(&"".into()).unwrap_err().to_string(),
+        "day8.txt: empty forest \"\""
+    );
+}
 
-            score.push(scenic_score);
-        }
+#[test]
+fn test_small_forests() {
+    for (forest, visible) in [("1", 1), ("12\n34", 4)] {
+        let trees = Day8.parse(&forest.into()).unwrap();
+
+        assert_eq!(Day8.part_1(&trees).unwrap(), visible);
+        assert_eq!(Day8.part_2(&trees).unwrap(), 0);
     }
+}
 
-    let max_score = score.iter().max().unwrap();
-    println!("{:?}", max_score);
+#[test]
+fn test_example_part_1() {
+    assert_eq!(
+        Day8.part_1(&Day8.parse(&crate::file::read_example(8)).unwrap())
+            .unwrap(),
+        21
+    );
 }
 
 #[test]
-fn test_part_2() {
-    part_2()
+fn test_example_part_2() {
+    assert_eq!(
+        Day8.part_2(&Day8.parse(&crate::file::read_example(8)).unwrap())
+            .unwrap(),
+        8
+    );
 }
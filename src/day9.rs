@@ -2,7 +2,13 @@ use std::collections::HashSet;
 
 use itertools::Itertools;
 
-use crate::file::read_lines;
+use crate::{
+    error::{parse_lines, parse_number, Error, Result},
+    input::Input,
+    solution::Solution,
+};
+
+const DAY: u8 = 9;
 
 fn distance_to(head_position: (i32, i32), tail_position: (i32, i32)) -> f32 {
     ((tail_position.0 as f32 - head_position.0 as f32).powf(2.0)
@@ -38,130 +44,188 @@ fn update_tail(head_position: (i32, i32), tail_position: (i32, i32)) -> (i32, i3
     }
 }
 
-fn directions() -> Vec<(String, i32)> {
-    read_lines("day9.txt")
-        .map(|line| {
-            line.unwrap()
-                .split_whitespace()
-                .map(|x| x.to_string())
-                .collect::<Vec<_>>()
-        })
-        .map(|direction| (direction[0].clone(), direction[1].parse::<i32>().unwrap()))
-        .collect()
-}
-
-pub fn part_1() {
-    let directions = directions();
+pub struct Day9;
 
-    let mut head_position = (0, 0);
-    let mut tail_position = (0, 0);
+impl Solution for Day9 {
+    type Input = Vec<(String, i32)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let mut visited = HashSet::new();
+    fn day(&self) -> u8 {
+        DAY
+    }
 
-    for (direction, length) in directions {
-        match direction.as_str() {
-            "R" => {
-                for _ in 0..length {
-                    head_position.0 += 1;
-                    tail_position = update_tail(head_position, tail_position);
-                    visited.insert(tail_position);
+    fn parse(&self, input: &Input) -> Result<Self::Input> {
+        parse_lines(input.as_str(), |line| {
+            match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [direction @ ("R" | "L" | "U" | "D"), length] => {
+                    Ok((direction.to_string(), parse_number(DAY, length)?))
                 }
+                [direction, _] => Err(Error::parse(DAY, "unknown direction", direction)),
+                _ => Err(Error::parse(DAY, "invalid motion", line)),
             }
-            "L" => {
-                for _ in 0..length {
-                    head_position.0 -= 1;
-                    tail_position = update_tail(head_position, tail_position);
-                    visited.insert(tail_position);
+        })
+    }
+
+    fn part_1(&self, directions: &Self::Input) -> Result<Self::Answer1> {
+        let mut head_position = (0, 0);
+        let mut tail_position = (0, 0);
+
+        let mut visited = HashSet::new();
+
+        for (direction, length) in directions {
+            match direction.as_str() {
+                "R" => {
+                    for _ in 0..*length {
+                        head_position.0 += 1;
+                        tail_position = update_tail(head_position, tail_position);
+                        visited.insert(tail_position);
+                    }
                 }
-            }
-            "U" => {
-                for _ in 0..length {
-                    head_position.1 += 1;
-                    tail_position = update_tail(head_position, tail_position);
-                    visited.insert(tail_position);
+                "L" => {
+                    for _ in 0..*length {
+                        head_position.0 -= 1;
+                        tail_position = update_tail(head_position, tail_position);
+                        visited.insert(tail_position);
+                    }
                 }
-            }
-            "D" => {
-                for _ in 0..length {
-                    head_position.1 -= 1;
-                    tail_position = update_tail(head_position, tail_position);
-                    visited.insert(tail_position);
+                "U" => {
+                    for _ in 0..*length {
+                        head_position.1 += 1;
+                        tail_position = update_tail(head_position, tail_position);
+                        visited.insert(tail_position);
+                    }
                 }
+                "D" => {
+                    for _ in 0..*length {
+                        head_position.1 -= 1;
+                        tail_position = update_tail(head_position, tail_position);
+                        visited.insert(tail_position);
+                    }
+                }
+                _ => panic!("direction not supported"),
             }
-            _ => panic!("direction not supported"),
         }
-    }
-
-    println!("{:?}", visited.len());
-}
-
-#[test]
-fn test_part_1() {
-    part_1()
-}
 
-pub fn part_2() {
-    let directions = directions();
+        Ok(visited.len())
+    }
 
-    let mut head_position = (0, 0);
-    let mut rope = vec![(0, 0); 10];
+    fn part_2(&self, directions: &Self::Input) -> Result<Self::Answer2> {
+        let mut head_position = (0, 0);
+        let mut rope = [(0, 0); 10];
 
-    let mut visited = HashSet::new();
+        let mut visited = HashSet::new();
 
-    for (direction, length) in directions {
-        match direction.as_str() {
-            "R" => {
-                for _ in 0..length {
-                    head_position.0 += 1;
-                    rope[0] = head_position;
+        for (direction, length) in directions {
+            match direction.as_str() {
+                "R" => {
+                    for _ in 0..*length {
+                        head_position.0 += 1;
+                        rope[0] = head_position;
 
-                    for (prev, current) in (0..10).tuple_windows() {
-                        rope[current] = update_tail(rope[prev], rope[current]);
+                        for (prev, current) in (0..10).tuple_windows() {
+                            rope[current] = update_tail(rope[prev], rope[current]);
+                        }
+                        visited.insert(*rope.last().unwrap());
                     }
-                    visited.insert(rope.last().unwrap().clone());
                 }
-            }
-            "L" => {
-                for _ in 0..length {
-                    head_position.0 -= 1;
-                    rope[0] = head_position;
-
-                    for (prev, current) in (0..10).tuple_windows() {
-                        rope[current] = update_tail(rope[prev], rope[current]);
+                "L" => {
+                    for _ in 0..*length {
+                        head_position.0 -= 1;
+                        rope[0] = head_position;
+
+                        for (prev, current) in (0..10).tuple_windows() {
+                            rope[current] = update_tail(rope[prev], rope[current]);
+                        }
+                        visited.insert(*rope.last().unwrap());
                     }
-                    visited.insert(rope.last().unwrap().clone());
                 }
-            }
-            "U" => {
-                for _ in 0..length {
-                    head_position.1 += 1;
-                    rope[0] = head_position;
-
-                    for (prev, current) in (0..10).tuple_windows() {
-                        rope[current] = update_tail(rope[prev], rope[current]);
+                "U" => {
+                    for _ in 0..*length {
+                        head_position.1 += 1;
+                        rope[0] = head_position;
+
+                        for (prev, current) in (0..10).tuple_windows() {
+                            rope[current] = update_tail(rope[prev], rope[current]);
+                        }
+                        visited.insert(*rope.last().unwrap());
                     }
-                    visited.insert(rope.last().unwrap().clone());
                 }
-            }
-            "D" => {
-                for _ in 0..length {
-                    head_position.1 -= 1;
-                    rope[0] = head_position;
-
-                    for (prev, current) in (0..10).tuple_windows() {
-                        rope[current] = update_tail(rope[prev], rope[current]);
+                "D" => {
+                    for _ in 0..*length {
+                        head_position.1 -= 1;
+                        rope[0] = head_position;
+
+                        for (prev, current) in (0..10).tuple_windows() {
+                            rope[current] = update_tail(rope[prev], rope[current]);
+                        }
+                        visited.insert(*rope.last().unwrap());
                     }
-                    visited.insert(rope.last().unwrap().clone());
                 }
+                _ => panic!("direction not supported"),
             }
-            _ => panic!("direction not supported"),
         }
+
+        Ok(visited.len())
     }
+}
 
-    println!("{:?}", visited.len());
+#[test]
+#[ignore = "reads the full puzzle input"]
+fn test_part_1() {
+    assert_eq!(
+        Day9.part_1(&Day9.parse(&crate::file::read_input(9)).unwrap())
+            .unwrap(),
+        6037
+    );
 }
 
 #[test]
+#[ignore = "reads the full puzzle input"]
 fn test_part_2() {
-    part_2()
+    assert_eq!(
+        Day9.part_2(&Day9.parse(&crate::file::read_input(9)).unwrap())
+            .unwrap(),
+        2485
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Day9.parse(&"R 4\nQ 2".into()).unwrap_err().to_string(),
+        "day9.txt:2: unknown direction \"Q\""
+    );
+    assert_eq!(
+        Day9.parse(&"R".into()).unwrap_err().to_string(),
+        "day9.txt:1: invalid motion \"R\""
+    );
+}
+
+#[test]
+fn test_example_part_1() {
+    assert_eq!(
+        Day9.part_1(&Day9.parse(&crate::file::read_example(9)).unwrap())
+            .unwrap(),
+        13
+    );
+}
+
+#[test]
+fn test_example_part_2() {
+    assert_eq!(
+        Day9.part_2(&Day9.parse(&crate::file::read_example(9)).unwrap())
+            .unwrap(),
+        1
+    );
+}
+
+#[test]
+fn test_larger_example_part_2() {
+    let input = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20\n";
+
+    assert_eq!(
+        Day9.part_2(&Day9.parse(&input.into()).unwrap()).unwrap(),
+        36
+    );
 }
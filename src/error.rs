@@ -0,0 +1,168 @@
+use std::{fmt::Display, io, str::FromStr};
+
+#[derive(Debug)]
+pub enum Error {
+    /// Malformed puzzle input. `line` is 1-based, 0 when the problem is not
+    /// tied to a single line.
+    Parse {
+        day: u8,
+        line: usize,
+        message: String,
+        text: String,
+    },
+    /// A puzzle input that parses, but has no answer.
+    NoAnswer {
+        day: u8,
+        message: String,
+    },
+    Io {
+        name: String,
+        source: io::Error,
+    },
+    /// A request to the puzzle server failed.
+    Http {
+        url: String,
+        message: String,
+    },
+    /// A malformed answers or submission history file.
+    Answers {
+        name: String,
+        message: String,
+    },
+    /// Some answers did not match the recorded ones when verifying.
+    Verification {
+        failed: usize,
+        total: usize,
+    },
+    /// Some parts failed when running with the JSON format, which reports
+    /// every failure before giving up.
+    Failed {
+        failed: usize,
+        total: usize,
+    },
+    /// An answer that should not be submitted.
+    Submission(String),
+    /// Generating a new day failed.
+    Scaffold(String),
+    UnknownDay(u8),
+    UnknownPart(u8),
+    Usage(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn parse(day: u8, message: &str, text: &str) -> Self {
+        Error::Parse {
+            day,
+            line: 0,
+            message: message.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    pub fn no_answer(day: u8, message: &str) -> Self {
+        Error::NoAnswer {
+            day,
+            message: message.to_string(),
+        }
+    }
+
+    /// Attaches a line number to a parse error that does not have one yet.
+    pub fn at_line(self, number: usize) -> Self {
+        match self {
+            Error::Parse {
+                day,
+                line: 0,
+                message,
+                text,
+            } => Error::Parse {
+                day,
+                line: number,
+                message,
+                text,
+            },
+            error => error,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse {
+                day,
+                line: 0,
+                message,
+                text,
+            } => write!(f, "day{}.txt: {} {:?}", day, message, text),
+            Error::Parse {
+                day,
+                line,
+                message,
+                text,
+            } => write!(f, "day{}.txt:{}: {} {:?}", day, line, message, text),
+            Error::NoAnswer { day, message } => write!(f, "day{}.txt: {}", day, message),
+            Error::Io { name, source } => write!(f, "{}: {}", name, source),
+            Error::Http { url, message } => write!(f, "{}: {}", url, message),
+            Error::Answers { name, message } => write!(f, "{}: {}", name, message),
+            Error::Verification { failed, total } => {
+                write!(f, "{} of {} answers did not verify", failed, total)
+            }
+            Error::Failed { failed, total } => write!(f, "{} of {} parts failed", failed, total),
+            Error::Submission(message) => write!(f, "{}", message),
+            Error::Scaffold(message) => write!(f, "{}", message),
+            Error::UnknownDay(day) => write!(f, "unknown day {}", day),
+            Error::UnknownPart(part) => write!(f, "unknown part {}", part),
+            Error::Usage(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Parses every line of `input` with `f`, numbering any parse error with the
+/// line it came from.
+pub fn parse_lines<T, F>(input: &str, mut f: F) -> Result<Vec<T>>
+where
+    F: FnMut(&str) -> Result<T>,
+{
+    input
+        .lines()
+        .zip(1..)
+        .map(|(line, number)| f(line).map_err(|error| error.at_line(number)))
+        .collect()
+}
+
+pub fn parse_number<T: FromStr>(day: u8, text: &str) -> Result<T> {
+    text.parse()
+        .map_err(|_| Error::parse(day, "invalid number", text))
+}
+
+#[test]
+fn test_display_parse_error() {
+    let error = Error::parse(9, "unknown direction", "Q").at_line(42);
+    assert_eq!(error.to_string(), "day9.txt:42: unknown direction \"Q\"");
+
+    let error = Error::parse(12, "missing start position", "S");
+    assert_eq!(error.to_string(), "day12.txt: missing start position \"S\"");
+
+    let error = Error::no_answer(6, "no marker found");
+    assert_eq!(error.to_string(), "day6.txt: no marker found");
+}
+
+#[test]
+fn test_parse_lines() {
+    let numbers = parse_lines("1\n2\n3", |line| parse_number::<i32>(1, line));
+    assert_eq!(numbers.unwrap(), vec![1, 2, 3]);
+
+    let error = parse_lines("1\nx\n3", |line| parse_number::<i32>(1, line)).unwrap_err();
+    assert_eq!(error.to_string(), "day1.txt:2: invalid number \"x\"");
+}
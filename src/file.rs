@@ -1,9 +1,20 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
-
-pub fn read_lines(filename: &str) -> io::Lines<io::BufReader<File>> {
-    let file = File::open(filename).unwrap();
-    io::BufReader::new(file).lines()
+#![cfg(test)]
+
+use std::path::Path;
+
+use crate::input::{Input, InputSource};
+
+/// Reads a puzzle input checked into the repository, independent of the
+/// directory the tests are launched from.
+pub fn read_input(day: u8) -> Input {
+    InputSource::Dir(Path::new(env!("CARGO_MANIFEST_DIR")).to_path_buf())
+        .read(day)
+        .unwrap()
+}
+
+/// Reads the example input from the puzzle description, kept in `fixtures/`.
+pub fn read_example(day: u8) -> Input {
+    InputSource::Dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures"))
+        .read(day)
+        .unwrap()
 }
@@ -36,7 +36,7 @@ where
                 write!(f, "{}", self.data.get(&(x, y)).map_or(T::default(), |e| *e))?;
             }
 
-            writeln!(f, "")?;
+            writeln!(f)?;
         }
 
         Ok(())
@@ -44,11 +44,10 @@ where
 }
 
 pub fn range_inclusive(a: i64, b: i64) -> impl Iterator<Item = i64> {
-    let x: Box<dyn Iterator<Item = i64>>;
-    if b > a {
-        x = Box::new(a..=b)
+    let x: Box<dyn Iterator<Item = i64>> = if b > a {
+        Box::new(a..=b)
     } else {
-        x = Box::new((b..=a).rev())
-    }
+        Box::new((b..=a).rev())
+    };
     x
 }
@@ -0,0 +1,188 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{Error, Result},
+    paragraphs::{NumberedParagraphs, ParagraphsTrait},
+};
+
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+
+/// Where the puzzle input for a day is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// A single file, used for whichever day is run.
+    File(PathBuf),
+    /// A directory holding one `dayN.txt` file per day.
+    Dir(PathBuf),
+    Stdin,
+}
+
+impl Default for InputSource {
+    /// `dayN.txt` in the directory named by `AOC_INPUTS_DIR`, or in the
+    /// current working directory when it is not set.
+    fn default() -> Self {
+        InputSource::Dir(
+            env::var_os(INPUTS_DIR_VAR)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(".")),
+        )
+    }
+}
+
+impl InputSource {
+    /// An explicit `--input` path wins over an inputs directory, and `-`
+    /// selects stdin.
+    pub fn resolve(input: Option<PathBuf>, inputs_dir: Option<PathBuf>) -> Self {
+        match (input, inputs_dir) {
+            (Some(path), _) if path == Path::new("-") => InputSource::Stdin,
+            (Some(path), _) => InputSource::File(path),
+            (None, Some(dir)) => InputSource::Dir(dir),
+            (None, None) => InputSource::default(),
+        }
+    }
+
+    /// Whether the source can only serve a single day.
+    pub fn is_single(&self) -> bool {
+        !matches!(self, InputSource::Dir(_))
+    }
+
+    pub fn name(&self, day: u8) -> String {
+        match self {
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Dir(dir) => dir.join(file_name(day)).display().to_string(),
+            InputSource::Stdin => "<stdin>".to_string(),
+        }
+    }
+
+    pub fn read(&self, day: u8) -> Result<Input> {
+        let input = match self {
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Dir(dir) => fs::read_to_string(dir.join(file_name(day))),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map(|_| input)
+            }
+        };
+
+        input.map(Input::from).map_err(|source| Error::Io {
+            name: self.name(day),
+            source,
+        })
+    }
+}
+
+/// The puzzle input of a day. Line endings are normalised to `\n` and
+/// trailing newlines are dropped, so that every view of it looks the same
+/// whether the file was saved with CRLF endings or with extra blank lines at
+/// the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    text: String,
+}
+
+impl Input {
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.text.lines()
+    }
+
+    /// Blocks of lines separated by one or more blank lines, each line
+    /// paired with its 1-based line number for error reporting.
+    pub fn paragraphs(&self) -> NumberedParagraphs<std::str::Lines<'_>> {
+        self.lines().numbered_paragraphs()
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        self.text.as_bytes()
+    }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self {
+        let text = if text.contains('\r') {
+            text.replace("\r\n", "\n")
+        } else {
+            text
+        };
+
+        Input {
+            text: text.trim_end_matches('\n').to_string(),
+        }
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Input::from(text.to_string())
+    }
+}
+
+pub fn file_name(day: u8) -> String {
+    format!("day{}.txt", day)
+}
+
+#[test]
+fn test_resolve() {
+    assert_eq!(
+        InputSource::resolve(Some("-".into()), Some("inputs".into())),
+        InputSource::Stdin
+    );
+    assert_eq!(
+        InputSource::resolve(Some("my.txt".into()), Some("inputs".into())),
+        InputSource::File("my.txt".into())
+    );
+    assert_eq!(
+        InputSource::resolve(None, Some("inputs".into())),
+        InputSource::Dir("inputs".into())
+    );
+}
+
+#[test]
+fn test_read_from_dir() {
+    let source = InputSource::Dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures"));
+
+    assert!(!source.is_single());
+    assert!(source.name(6).ends_with("day6.txt"));
+    assert_eq!(source.read(6).unwrap().as_str().len(), 30);
+}
+
+#[test]
+fn test_input_views() {
+    let input = Input::from("1000\r\n2000\r\n\r\n \r\n3000\r\n\r\n");
+
+    assert_eq!(input.as_str(), "1000\n2000\n\n \n3000");
+    assert_eq!(
+        input.lines().collect::<Vec<_>>(),
+        ["1000", "2000", "", " ", "3000"]
+    );
+    assert_eq!(
+        input.paragraphs().collect::<Vec<_>>(),
+        [vec![("1000", 1), ("2000", 2)], vec![("3000", 5)]]
+    );
+    assert_eq!(input.bytes().len(), 17);
+    assert_eq!(Input::from("    [D]\n"), Input::from("    [D]"));
+}
+
+#[test]
+fn test_line_endings_do_not_change_answers() {
+    let examples = InputSource::Dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures"));
+
+    for solution in crate::registry::SOLUTIONS {
+        let input = examples.read(solution.day()).unwrap();
+        let crlf = Input::from(input.as_str().replace('\n', "\r\n") + "\r\n\r\n");
+
+        assert_eq!(
+            solution.run(&crlf, 1).unwrap(),
+            solution.run(&input, 1).unwrap(),
+            "day {}",
+            solution.day()
+        );
+    }
+}
@@ -0,0 +1,39 @@
+//! Advent of Code 2022 solutions, with the shared parsing helpers they are
+//! built on and the command-line front-end in `cli`.
+
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod error;
+mod file;
+pub mod grid;
+pub mod group_by;
+pub mod input;
+pub mod lines_as_numbers;
+mod mock_server;
+pub mod paragraphs;
+pub mod pool;
+pub mod registry;
+pub mod scaffold;
+pub mod server;
+pub mod solution;
+pub mod submit;
+pub mod summed_groups;
+pub mod top_k;
+pub mod watch;
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
@@ -1,35 +1,91 @@
-use std::io::Error;
+use std::{marker::PhantomData, str::FromStr};
 
-pub struct LinesAsNumbers<I> {
+use crate::error::{parse_number, Result};
+
+pub struct LinesAsNumbers<I, T> {
     iter: I,
+    day: u8,
+    line: usize,
+    number: PhantomData<T>,
 }
 
-impl<I> Iterator for LinesAsNumbers<I>
+impl<I, T> Iterator for LinesAsNumbers<I, T>
 where
-    I: Iterator<Item = Result<String, Error>>,
+    I: Iterator,
+    I::Item: AsRef<str>,
+    T: FromStr,
 {
-    type Item = i32;
+    type Item = Result<Option<T>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(line) = self.iter.next() {
-            if let Ok(line) = line {
-                return Some(line.parse::<Self::Item>().map_or_else(|_| 0, |x| x));
-            }
+        let line = self.iter.next()?;
+        let text = line.as_ref().trim();
+        self.line += 1;
+
+        if text.is_empty() {
+            return Some(Ok(None));
         }
 
-        None
+        Some(
+            parse_number(self.day, text)
+                .map(Some)
+                .map_err(|error| error.at_line(self.line)),
+        )
     }
 }
 
 pub trait StringToNumbersTrait<I> {
-    fn as_numbers(self) -> LinesAsNumbers<I>;
+    /// Parses lines of the puzzle input of `day` as numbers. Blank lines come
+    /// out as `None`, so that they can serve as separators, and any other line
+    /// that is not a number as an error at that line.
+    #[allow(clippy::wrong_self_convention)]
+    fn as_numbers<T: FromStr>(self, day: u8) -> LinesAsNumbers<I, T>;
 }
 
 impl<I> StringToNumbersTrait<I> for I
 where
     I: Iterator,
 {
-    fn as_numbers(self) -> LinesAsNumbers<I> {
-        LinesAsNumbers { iter: self }
+    fn as_numbers<T: FromStr>(self, day: u8) -> LinesAsNumbers<I, T> {
+        LinesAsNumbers {
+            iter: self,
+            day,
+            line: 0,
+            number: PhantomData,
+        }
     }
 }
+
+#[test]
+fn test_as_numbers() {
+    let numbers = "100\n\n0\n-5"
+        .lines()
+        .as_numbers::<i32>(1)
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(numbers, vec![Some(100), None, Some(0), Some(-5)]);
+
+    let error = "1\n\nx"
+        .lines()
+        .as_numbers::<u8>(1)
+        .collect::<Result<Vec<_>>>()
+        .unwrap_err();
+    assert_eq!(error.to_string(), "day1.txt:3: invalid number \"x\"");
+}
+
+#[test]
+fn test_as_numbers_generic() {
+    let numbers = "18446744073709551615\n1.5"
+        .lines()
+        .as_numbers::<f64>(1)
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(numbers, vec![Some(18446744073709551615.0), Some(1.5)]);
+
+    let numbers = "170141183460469231731687303715884105727"
+        .lines()
+        .as_numbers::<i128>(1)
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(numbers, vec![Some(i128::MAX)]);
+}
@@ -1,68 +1,78 @@
-mod file;
-mod group_by;
-mod lines_as_numbers;
-mod summed_groups;
-mod grid;
-
-mod day1;
-mod day10;
-mod day11;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-
-fn main() {
-    day1::part_1();
-    day1::part_2();
-
-    day2::part_1();
-    day2::part_2();
-
-    day3::part_1();
-    day3::part_2();
-
-    day4::part_1();
-    day4::part_2();
-
-    day5::part_1();
-    day5::part_2();
-
-    day6::part_1();
-    day6::part_2();
-
-    day7::part_1();
-    day7::part_2();
-
-    day8::part_1();
-    day8::part_2();
-
-    day9::part_1();
-    day9::part_2();
-
-    day10::part_1();
-    day10::part_2();
-
-    day11::part_1();
-    day11::part_2();
-
-    day12::part_1();
-    day12::part_2();
-
-    day13::part_1();
-    day13::part_2();
-
-    day14::part_1();
-    day14::part_2();
-
-    day15::part_1();
-    day15::part_2();
+use std::{process::ExitCode, time::Duration};
+
+use clap::Parser;
+
+use aoc2022::{
+    answers::Answers,
+    cli::{self, Cli, Command},
+    day2::Mapping,
+    scaffold, watch,
+};
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let source = cli.input_source();
+
+    let result = match cli.command {
+        Command::Run {
+            day,
+            part,
+            all: _,
+            format,
+            time,
+            parallel,
+            jobs,
+        } => cli::run(&source, day, part, format, time, cli::jobs(parallel, jobs)),
+        Command::Bench {
+            day,
+            part,
+            iterations,
+            format,
+        } => cli::bench(&source, day, part, iterations, format),
+        Command::Fetch { day, server } => cli::fetch(&source, day, &server.server()),
+        Command::Submit {
+            day,
+            part,
+            server,
+            history,
+        } => cli::submit(&source, day, part, &server.server(), &history),
+        Command::Watch {
+            day,
+            root,
+            interval,
+        } => watch::watch(&root, &source, day, Duration::from_millis(interval)),
+        Command::NewDay { day, root } => scaffold::new_day(&root, &source, day).map(|touched| {
+            for path in touched {
+                println!("{}", path.display());
+            }
+        }),
+        Command::Guide {
+            opponent,
+            hands,
+            outcomes,
+            search,
+            stats,
+            format,
+        } => cli::guide(
+            &source,
+            Mapping {
+                opponent: &opponent,
+                hands: &hands,
+                outcomes: &outcomes,
+            },
+            search,
+            stats.then_some(format),
+        ),
+        Command::Verify { day, answers } => {
+            Answers::read(&answers).and_then(|answers| cli::verify(&source, day, &answers))
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,81 @@
+#![cfg(test)]
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread::{self, JoinHandle},
+};
+
+/// A request as seen by the mock server.
+#[derive(Debug)]
+pub struct Request {
+    /// For example `GET /day/1/input`.
+    pub line: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Serves one canned `(status, body)` response per connection, in order, on
+/// a local port. Returns the base URL to point clients at, and a handle that
+/// yields the requests that were received once every response has been sent.
+pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<Request>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let mut requests = vec![];
+
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+
+            let mut headers = vec![];
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                match header.trim_end().split_once(": ") {
+                    Some((name, value)) => headers.push((name.to_string(), value.to_string())),
+                    None => break,
+                }
+            }
+
+            let length = headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                .map_or(0, |(_, value)| value.parse().unwrap());
+            let mut request_body = vec![0; length];
+            reader.read_exact(&mut request_body).unwrap();
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+
+            requests.push(Request {
+                line: line.trim_end().rsplit_once(' ').unwrap().0.to_string(),
+                headers,
+                body: String::from_utf8(request_body).unwrap(),
+            });
+        }
+
+        requests
+    });
+
+    (base_url, handle)
+}
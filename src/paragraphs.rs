@@ -0,0 +1,107 @@
+use std::{iter::Zip, ops::RangeFrom};
+
+pub struct NumberedParagraphs<I> {
+    lines: Zip<I, RangeFrom<usize>>,
+}
+
+impl<I> Iterator for NumberedParagraphs<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = Vec<(I::Item, usize)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut paragraph = vec![];
+
+        for (line, number) in self.lines.by_ref() {
+            if !line.as_ref().trim().is_empty() {
+                paragraph.push((line, number));
+            } else if !paragraph.is_empty() {
+                return Some(paragraph);
+            }
+        }
+
+        if !paragraph.is_empty() {
+            Some(paragraph)
+        } else {
+            None
+        }
+    }
+}
+
+pub struct Paragraphs<I> {
+    iter: NumberedParagraphs<I>,
+}
+
+impl<I> Iterator for Paragraphs<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|paragraph| paragraph.into_iter().map(|(line, _)| line).collect())
+    }
+}
+
+/// Groups lines into the paragraphs separated by blank lines, where a line
+/// of nothing but whitespace counts as blank. Runs of blank lines count as a
+/// single separator, so no paragraph is ever empty.
+pub trait ParagraphsTrait<I>
+where
+    I: Iterator,
+{
+    fn paragraphs(self) -> Paragraphs<I>;
+
+    /// Like [`ParagraphsTrait::paragraphs`], pairing every line with its
+    /// 1-based number for error reporting.
+    fn numbered_paragraphs(self) -> NumberedParagraphs<I>;
+}
+
+impl<I> ParagraphsTrait<I> for I
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    fn paragraphs(self) -> Paragraphs<I> {
+        Paragraphs {
+            iter: self.numbered_paragraphs(),
+        }
+    }
+
+    fn numbered_paragraphs(self) -> NumberedParagraphs<I> {
+        NumberedParagraphs {
+            lines: self.zip(1..),
+        }
+    }
+}
+
+#[test]
+fn test_paragraphs_trait() {
+    let lines = vec!["", "100", "200", "", "", "0", "  ", "600", ""];
+    let paragraphs = lines.into_iter().paragraphs().collect::<Vec<_>>();
+
+    assert_eq!(paragraphs, vec![vec!["100", "200"], vec!["0"], vec!["600"]]);
+}
+
+#[test]
+fn test_numbered_paragraphs() {
+    let paragraphs = "\na\nb\n\n\nc\n  \nd\n"
+        .lines()
+        .map(String::from)
+        .numbered_paragraphs()
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        paragraphs,
+        vec![
+            vec![("a".to_string(), 2), ("b".to_string(), 3)],
+            vec![("c".to_string(), 6)],
+            vec![("d".to_string(), 8)],
+        ]
+    );
+}
@@ -0,0 +1,57 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// Maps `f` over `items` on up to `threads` threads. Each thread picks the
+/// next unclaimed item, so a slow item does not hold up the ones after it,
+/// and the results are returned in the order of `items`.
+pub fn map_in_order<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(items.iter().map(|_| None).collect::<Vec<Option<R>>>());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let item = match items.get(index) {
+                    Some(item) => item,
+                    None => break,
+                };
+
+                let result = f(item);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.unwrap())
+        .collect()
+}
+
+#[test]
+fn test_map_in_order() {
+    let items: Vec<u64> = (0..20).collect();
+
+    let squares = map_in_order(&items, 4, |&n| {
+        // Let the early items finish last.
+        thread::sleep(std::time::Duration::from_millis(20 - n));
+        n * n
+    });
+
+    assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+    assert!(map_in_order(&[] as &[u64], 4, |&n| n).is_empty());
+    assert_eq!(map_in_order(&[1, 2], 0, |&n| n + 1), vec![2, 3]);
+}
@@ -0,0 +1,37 @@
+use crate::{
+    day1::Day1, day10::Day10, day11::Day11, day12::Day12, day13::Day13, day14::Day14, day15::Day15,
+    day2::Day2, day3::Day3, day4::Day4, day5::Day5, day6::Day6, day7::Day7, day8::Day8, day9::Day9,
+    solution::Runnable,
+};
+
+pub static SOLUTIONS: &[&dyn Runnable] = &[
+    &Day1,
+    &Day2::PUZZLE,
+    &Day3::PUZZLE,
+    &Day4,
+    &Day5,
+    &Day6,
+    &Day7,
+    &Day8,
+    &Day9,
+    &Day10,
+    &Day11,
+    &Day12,
+    &Day13,
+    &Day14,
+    &Day15::PUZZLE,
+];
+
+pub fn find(day: u8) -> Option<&'static dyn Runnable> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.day() == day)
+        .copied()
+}
+
+#[test]
+fn test_registry_is_ordered() {
+    let days: Vec<_> = SOLUTIONS.iter().map(|solution| solution.day()).collect();
+
+    assert!(days.windows(2).all(|pair| pair[0] < pair[1]));
+}
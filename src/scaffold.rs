@@ -0,0 +1,309 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use regex::Regex;
+
+use crate::{
+    error::{Error, Result},
+    input::{file_name, InputSource},
+};
+
+/// Skeleton of a new day, with stub parts and a test against the example
+/// input that is expected to be filled in along with the solution.
+pub fn module_source(day: u8) -> String {
+    format!(
+        r#"use crate::{{
+    error::{{parse_lines, Result}},
+    input::Input,
+    solution::Solution,
+}};
+
+const DAY: u8 = {day};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn day(&self) -> u8 {{
+        DAY
+    }}
+
+    fn parse(&self, input: &Input) -> Result<Self::Input> {{
+        parse_lines(input.as_str(), |line| Ok(line.to_string()))
+    }}
+
+    fn part_1(&self, _input: &Self::Input) -> Result<Self::Answer1> {{
+        Ok(0)
+    }}
+
+    fn part_2(&self, _input: &Self::Input) -> Result<Self::Answer2> {{
+        Ok(0)
+    }}
+}}
+
+#[test]
+fn test_example_part_1() {{
+    assert_eq!(
+        Day{day}
+            .part_1(&Day{day}.parse(&crate::file::read_example({day})).unwrap())
+            .unwrap(),
+        0
+    );
+}}
+
+#[test]
+fn test_example_part_2() {{
+    assert_eq!(
+        Day{day}
+            .part_2(&Day{day}.parse(&crate::file::read_example({day})).unwrap())
+            .unwrap(),
+        0
+    );
+}}
+"#,
+        day = day
+    )
+}
+
+/// Adds `pub mod dayN;` to the day modules of `lib.rs`, keeping them in the
+/// order rustfmt sorts them in.
+pub fn add_module(lib: &str, day: u8) -> Result<String> {
+    let declaration = format!("pub mod day{};", day);
+    let is_day = |line: &str| line.starts_with("pub mod day");
+
+    let lines: Vec<_> = lib.lines().collect();
+    let first = lines
+        .iter()
+        .position(|line| is_day(line))
+        .ok_or_else(|| Error::Scaffold("no day modules in src/lib.rs".to_string()))?;
+    let count = lines[first..]
+        .iter()
+        .take_while(|line| is_day(line))
+        .count();
+
+    let mut days = lines[first..first + count].to_vec();
+    if days.contains(&declaration.as_str()) {
+        return Err(Error::Scaffold(format!("day{} is already declared", day)));
+    }
+    days.push(&declaration);
+    days.sort_unstable_by_key(|line| line.trim_end_matches(';'));
+
+    let mut lines = lines;
+    lines.splice(first..first + count, days);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Imports `DayN` in `registry.rs` and adds it to `SOLUTIONS`, in day order.
+pub fn register(registry: &str, day: u8) -> Result<String> {
+    let missing = |what: &str| Error::Scaffold(format!("no {} in src/registry.rs", what));
+
+    let (start, end) = registry
+        .find("SOLUTIONS")
+        .and_then(|start| {
+            let open = start + registry[start..].find("&[")? + 2;
+            let close = open + registry[open..].find("\n];")?;
+            Some((open, close))
+        })
+        .ok_or_else(|| missing("SOLUTIONS list"))?;
+
+    let entry = Regex::new(r"&Day(\d+)").unwrap();
+    let mut later = None;
+    for captures in entry.captures_iter(&registry[start..end]) {
+        let registered: u8 = captures[1].parse().unwrap_or(0);
+
+        if registered == day {
+            return Err(Error::Scaffold(format!("day{} is already registered", day)));
+        }
+        if registered > day && later.is_none() {
+            later = Some(start + captures.get(0).unwrap().start());
+        }
+    }
+
+    let import = registry
+        .find("solution::Runnable")
+        .ok_or_else(|| missing("import of Runnable"))?;
+
+    let mut registry = registry.to_string();
+    match later {
+        Some(position) => registry.insert_str(position, &format!("&Day{}, ", day)),
+        None => registry.insert_str(end, &format!(" &Day{},", day)),
+    }
+    // The import comes before the list, so inserting it last leaves the
+    // positions above valid.
+    registry.insert_str(import, &format!("day{}::Day{}, ", day, day));
+
+    Ok(registry)
+}
+
+/// Creates `path` empty, leaving it alone when it already exists.
+fn touch(path: &Path) -> Result<bool> {
+    if path.exists() {
+        return Ok(false);
+    }
+
+    fs::write(path, "").map_err(|source| Error::Io {
+        name: path.display().to_string(),
+        source,
+    })?;
+
+    Ok(true)
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|source| Error::Io {
+        name: path.display().to_string(),
+        source,
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).map_err(|source| Error::Io {
+        name: path.display().to_string(),
+        source,
+    })
+}
+
+/// Generates the module for `day` in the crate at `root`, registers it and
+/// creates empty example and puzzle inputs. Returns the files it touched.
+pub fn new_day(root: &Path, source: &InputSource, day: u8) -> Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::UnknownDay(day));
+    }
+
+    let module = root.join("src").join(format!("day{}.rs", day));
+    if module.exists() {
+        return Err(Error::Scaffold(format!("day {} already exists", day)));
+    }
+
+    let lib = root.join("src").join("lib.rs");
+    let registry = root.join("src").join("registry.rs");
+
+    // Work out every edit before writing anything, so that a failure does
+    // not leave a half registered day behind.
+    let lib_source = add_module(&read(&lib)?, day)?;
+    let registry_source = register(&read(&registry)?, day)?;
+
+    write(&module, &module_source(day))?;
+    write(&lib, &lib_source)?;
+    write(&registry, &registry_source)?;
+
+    // The edits are valid Rust but not formatted like the rest of the file.
+    let _ = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .arg(&registry)
+        .status();
+
+    let mut touched = vec![module, lib, registry];
+
+    let example = root.join("fixtures").join(file_name(day));
+    if touch(&example)? {
+        touched.push(example);
+    }
+
+    if let InputSource::Dir(dir) = source {
+        let input = dir.join(file_name(day));
+        if touch(&input)? {
+            touched.push(input);
+        }
+    }
+
+    Ok(touched)
+}
+
+#[test]
+fn test_add_module() {
+    let lib = "pub mod solution;\n\npub mod day1;\npub mod day15;\npub mod day2;\npub mod day9;\n";
+
+    assert_eq!(
+        add_module(lib, 16).unwrap(),
+        "pub mod solution;\n\npub mod day1;\npub mod day15;\npub mod day16;\npub mod day2;\npub mod day9;\n"
+    );
+    assert!(matches!(add_module(lib, 2), Err(Error::Scaffold(_))));
+}
+
+#[test]
+fn test_register() {
+    let registry = concat!(
+        "use crate::{day1::Day1, day2::Day2, solution::Runnable};\n",
+        "\n",
+        "pub static SOLUTIONS: &[&dyn Runnable] = &[\n",
+        "    &Day1, &Day2,\n",
+        "];\n",
+    );
+
+    assert_eq!(
+        register(registry, 3).unwrap(),
+        concat!(
+            "use crate::{day1::Day1, day2::Day2, day3::Day3, solution::Runnable};\n",
+            "\n",
+            "pub static SOLUTIONS: &[&dyn Runnable] = &[\n",
+            "    &Day1, &Day2, &Day3,\n",
+            "];\n",
+        )
+    );
+    assert!(matches!(register(registry, 2), Err(Error::Scaffold(_))));
+    assert!(matches!(register("", 16), Err(Error::Scaffold(_))));
+
+    let registry = concat!(
+        "use crate::{day1::Day1, day4::Day4, solution::Runnable};\n",
+        "\n",
+        "pub static SOLUTIONS: &[&dyn Runnable] = &[\n",
+        "    &Day1,\n",
+        "    &Day4::PUZZLE,\n",
+        "];\n",
+    );
+    assert_eq!(
+        register(registry, 3).unwrap(),
+        concat!(
+            "use crate::{day1::Day1, day4::Day4, day3::Day3, solution::Runnable};\n",
+            "\n",
+            "pub static SOLUTIONS: &[&dyn Runnable] = &[\n",
+            "    &Day1,\n",
+            "    &Day3, &Day4::PUZZLE,\n",
+            "];\n",
+        )
+    );
+}
+
+#[test]
+fn test_new_day() {
+    let root = std::env::temp_dir().join(format!("aoc2022-scaffold-{}", std::process::id()));
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::create_dir_all(root.join("fixtures")).unwrap();
+    fs::write(root.join("src/lib.rs"), include_str!("lib.rs")).unwrap();
+    fs::write(root.join("src/registry.rs"), include_str!("registry.rs")).unwrap();
+
+    // The embedded files are this crate's own, so scaffold a day it lacks.
+    let day = (1..=25)
+        .rev()
+        .find(|&day| crate::registry::find(day).is_none())
+        .unwrap();
+    let source = InputSource::Dir(root.clone());
+    let touched = new_day(&root, &source, day).unwrap();
+
+    assert_eq!(touched.len(), 5);
+    assert!(read(&root.join(format!("src/day{}.rs", day)))
+        .unwrap()
+        .contains(&format!("impl Solution for Day{}", day)));
+    assert!(read(&root.join("src/lib.rs"))
+        .unwrap()
+        .contains(&format!("pub mod day{};", day)));
+    assert!(root.join(file_name(day)).exists());
+    assert!(matches!(
+        new_day(&root, &source, day),
+        Err(Error::Scaffold(_))
+    ));
+    assert!(matches!(
+        new_day(&root, &source, 26),
+        Err(Error::UnknownDay(26))
+    ));
+
+    fs::remove_dir_all(root).unwrap();
+}
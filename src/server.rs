@@ -0,0 +1,197 @@
+use std::{fs, path::Path, time::Duration};
+
+use crate::{
+    error::{Error, Result},
+    input::file_name,
+    solution::Answer,
+    submit::{parse_response, Outcome},
+};
+
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2022";
+
+/// The puzzle server, reached at `base_url` with the session cookie of a
+/// logged in user.
+pub struct Server {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Server {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Server {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(concat!(
+                    env!("CARGO_PKG_NAME"),
+                    "/",
+                    env!("CARGO_PKG_VERSION")
+                ))
+                .build(),
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn http_error(url: &str, error: ureq::Error) -> Error {
+        let message = match error {
+            ureq::Error::Status(status, response) => {
+                format!("server answered {} {}", status, response.status_text())
+            }
+            ureq::Error::Transport(transport) => transport.to_string(),
+        };
+
+        Error::Http {
+            url: url.to_string(),
+            message,
+        }
+    }
+
+    /// Downloads the puzzle input of `day`.
+    pub fn input(&self, day: u8) -> Result<String> {
+        let url = self.url(&format!("/day/{}/input", day));
+
+        self.agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|error| Server::http_error(&url, error))?
+            .into_string()
+            .map_err(|error| Error::Http {
+                url,
+                message: error.to_string(),
+            })
+    }
+
+    /// Submits `answer` for a part, returning how it was judged and how many
+    /// seconds to wait before submitting again.
+    pub fn submit(&self, day: u8, part: u8, answer: &Answer) -> Result<(Outcome, u64)> {
+        let url = self.url(&format!("/day/{}/answer", day));
+
+        let page = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[
+                ("level", &part.to_string()),
+                ("answer", &answer.to_string()),
+            ])
+            .map_err(|error| Server::http_error(&url, error))?
+            .into_string()
+            .map_err(|error| Error::Http {
+                url: url.clone(),
+                message: error.to_string(),
+            })?;
+
+        parse_response(&page).ok_or_else(|| Error::Http {
+            url,
+            message: "unexpected response to a submission".to_string(),
+        })
+    }
+}
+
+/// Makes sure the input of `day` is in `dir`, downloading it only when it is
+/// not there yet. Empty files, such as the placeholders created by `new-day`,
+/// do not count as cached. Returns whether the input was downloaded.
+pub fn cache_input(server: &Server, dir: &Path, day: u8) -> Result<bool> {
+    let path = dir.join(file_name(day));
+    let io_error = |source| Error::Io {
+        name: path.display().to_string(),
+        source,
+    };
+
+    if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(false);
+    }
+
+    let input = server.input(day)?;
+
+    // Write to a temporary file first so that an interrupted download never
+    // leaves a truncated input behind that would be taken for a cached one.
+    let partial = dir.join(format!("{}.partial", file_name(day)));
+    fs::create_dir_all(dir).map_err(io_error)?;
+    fs::write(&partial, input).map_err(io_error)?;
+    fs::rename(&partial, &path).map_err(io_error)?;
+
+    Ok(true)
+}
+
+#[test]
+fn test_input() {
+    let (base_url, requests) = crate::mock_server::serve(vec![(200, "1000\n2000\n")]);
+
+    let server = Server::new(&format!("{}/", base_url), "secret");
+    assert_eq!(server.input(1).unwrap(), "1000\n2000\n");
+
+    let requests = requests.join().unwrap();
+    assert_eq!(requests[0].line, "GET /day/1/input");
+    assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+}
+
+#[test]
+fn test_input_errors() {
+    let (base_url, requests) = crate::mock_server::serve(vec![(404, "Not Found")]);
+
+    let error = Server::new(&base_url, "secret").input(26).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!("{}/day/26/input: server answered 404 Mock", base_url)
+    );
+
+    requests.join().unwrap();
+}
+
+#[test]
+fn test_submit() {
+    let (base_url, requests) = crate::mock_server::serve(vec![
+        (200, "<p>That's the right answer!</p>"),
+        (200, "<p>Puzzle inputs differ by user.</p>"),
+    ]);
+    let server = Server::new(&base_url, "secret");
+
+    assert_eq!(
+        server
+            .submit(5, 2, &Answer::Text("MCD".to_string()))
+            .unwrap(),
+        (Outcome::Right, 0)
+    );
+    assert_eq!(
+        server
+            .submit(5, 2, &Answer::Number(1))
+            .unwrap_err()
+            .to_string(),
+        format!(
+            "{}/day/5/answer: unexpected response to a submission",
+            base_url
+        )
+    );
+
+    let requests = requests.join().unwrap();
+    assert_eq!(requests[0].line, "POST /day/5/answer");
+    assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+    assert_eq!(requests[0].body, "level=2&answer=MCD");
+}
+
+#[test]
+fn test_cache_input() {
+    let dir = std::env::temp_dir().join(format!("aoc2022-cache-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("day2.txt"), "").unwrap();
+
+    // Only one response: a second download would find no server to talk to.
+    let (base_url, requests) = crate::mock_server::serve(vec![(200, "A Y\n")]);
+    let server = Server::new(&base_url, "secret");
+
+    assert!(cache_input(&server, &dir, 2).unwrap());
+    assert!(!cache_input(&server, &dir, 2).unwrap());
+    assert_eq!(fs::read_to_string(dir.join("day2.txt")).unwrap(), "A Y\n");
+    assert_eq!(requests.join().unwrap().len(), 1);
+
+    fs::remove_dir_all(dir).unwrap();
+}
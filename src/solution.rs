@@ -0,0 +1,181 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+
+use crate::{
+    error::{Error, Result},
+    input::Input,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl Answer {
+    pub fn to_json(&self) -> Value {
+        match self {
+            Answer::Number(number) => json!(number),
+            Answer::Text(text) => json!(text),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(number: i32) -> Self {
+        Answer::Number(number as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(number: i64) -> Self {
+        Answer::Number(number)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(number: usize) -> Self {
+        Answer::Number(number as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+/// A single day of the calendar: parses the puzzle input once and solves
+/// both parts from the parsed representation.
+pub trait Solution {
+    type Input;
+    type Answer1: Into<Answer>;
+    type Answer2: Into<Answer>;
+
+    fn day(&self) -> u8;
+    fn parse(&self, input: &Input) -> Result<Self::Input>;
+    fn part_1(&self, input: &Self::Input) -> Result<Self::Answer1>;
+    fn part_2(&self, input: &Self::Input) -> Result<Self::Answer2>;
+}
+
+/// Object safe view of a [`Solution`] so that days with different input and
+/// answer types can be stored side by side in the registry.
+pub trait Runnable: Sync {
+    fn day(&self) -> u8;
+    fn run(&self, input: &Input, part: u8) -> Result<Answer>;
+
+    /// Like [`Runnable::run`], also measuring how long parsing and solving
+    /// took.
+    fn run_timed(&self, input: &Input, part: u8) -> Result<(Answer, Timing)>;
+}
+
+/// Time spent on the two stages of a run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl<S> Runnable for S
+where
+    S: Solution + Sync,
+{
+    fn day(&self) -> u8 {
+        Solution::day(self)
+    }
+
+    fn run(&self, input: &Input, part: u8) -> Result<Answer> {
+        match part {
+            1 => Ok(self.part_1(&self.parse(input)?)?.into()),
+            2 => Ok(self.part_2(&self.parse(input)?)?.into()),
+            _ => Err(Error::UnknownPart(part)),
+        }
+    }
+
+    fn run_timed(&self, input: &Input, part: u8) -> Result<(Answer, Timing)> {
+        if !(1..=2).contains(&part) {
+            return Err(Error::UnknownPart(part));
+        }
+
+        let start = Instant::now();
+        let parsed = self.parse(input)?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let answer = match part {
+            1 => self.part_1(&parsed)?.into(),
+            _ => self.part_2(&parsed)?.into(),
+        };
+        let solve = start.elapsed();
+
+        Ok((answer, Timing { parse, solve }))
+    }
+}
+
+#[test]
+fn test_runnable() {
+    struct Lengths;
+
+    impl Solution for Lengths {
+        type Input = Vec<String>;
+        type Answer1 = usize;
+        type Answer2 = String;
+
+        fn day(&self) -> u8 {
+            0
+        }
+
+        fn parse(&self, input: &Input) -> Result<Self::Input> {
+            Ok(input.lines().map(|line| line.to_string()).collect())
+        }
+
+        fn part_1(&self, input: &Self::Input) -> Result<Self::Answer1> {
+            match input.len() {
+                0 => Err(Error::no_answer(0, "no lines")),
+                lines => Ok(lines),
+            }
+        }
+
+        fn part_2(&self, input: &Self::Input) -> Result<Self::Answer2> {
+            Ok(input.join(","))
+        }
+    }
+
+    let runnable: &dyn Runnable = &Lengths;
+    let input = Input::from("a\nb\n");
+
+    assert_eq!(runnable.run(&input, 1).unwrap(), Answer::Number(2));
+    assert_eq!(
+        runnable.run(&input, 2).unwrap(),
+        Answer::Text("a,b".to_string())
+    );
+    assert!(matches!(
+        runnable.run(&input, 3),
+        Err(Error::UnknownPart(3))
+    ));
+
+    assert_eq!(
+        runnable.run(&Input::from(""), 1).unwrap_err().to_string(),
+        "day0.txt: no lines"
+    );
+
+    let (answer, _) = runnable.run_timed(&input, 1).unwrap();
+    assert_eq!(answer, Answer::Number(2));
+    assert!(matches!(
+        runnable.run_timed(&input, 0),
+        Err(Error::UnknownPart(0))
+    ));
+}
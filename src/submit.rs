@@ -0,0 +1,361 @@
+use std::{fmt::Display, fs, io, path::Path};
+
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::{
+    error::{Error, Result},
+    solution::Answer,
+};
+
+pub const HISTORY_FILE: &str = "submissions.json";
+
+/// How the puzzle server judged a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer was not looked at because the previous one was too recent.
+    RateLimited,
+    /// The part has been solved before, so the answer was not looked at.
+    AlreadySolved,
+}
+
+impl Outcome {
+    const NAMES: [(Outcome, &'static str); 6] = [
+        (Outcome::Right, "right"),
+        (Outcome::Wrong, "wrong"),
+        (Outcome::TooHigh, "too high"),
+        (Outcome::TooLow, "too low"),
+        (Outcome::RateLimited, "rate limited"),
+        (Outcome::AlreadySolved, "already solved"),
+    ];
+
+    fn name(self) -> &'static str {
+        Outcome::NAMES
+            .iter()
+            .find(|(outcome, _)| *outcome == self)
+            .unwrap()
+            .1
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Outcome::NAMES
+            .iter()
+            .find(|(_, known)| *known == name)
+            .map(|(outcome, _)| *outcome)
+    }
+
+    fn is_wrong(self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Reads the outcome of a submission, and how many seconds to wait before the
+/// next one, from the page the server answers with.
+pub fn parse_response(page: &str) -> Option<(Outcome, u64)> {
+    let outcome = if page.contains("That's the right answer") {
+        Outcome::Right
+    } else if page.contains("That's not the right answer") {
+        if page.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if page.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if page.contains("You gave an answer too recently") {
+        Outcome::RateLimited
+    } else if page.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        return None;
+    };
+
+    let left = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    let minutes = Regex::new(r"[Pp]lease wait (one|\d+) minutes?").unwrap();
+
+    let wait = if let Some(caps) = left.captures(page) {
+        let number = |index| {
+            caps.get(index)
+                .map_or(0, |m| m.as_str().parse().unwrap_or(0))
+        };
+        number(1) * 60 + number(2)
+    } else if let Some(caps) = minutes.captures(page) {
+        caps[1].parse::<u64>().unwrap_or(1) * 60
+    } else if outcome.is_wrong() {
+        60
+    } else {
+        0
+    };
+
+    Some((outcome, wait))
+}
+
+/// A submission, with the time it was made in seconds since the Unix epoch
+/// and the number of seconds the server asked to wait before the next one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub outcome: Outcome,
+    pub time: u64,
+    pub wait: u64,
+}
+
+impl Attempt {
+    fn to_json(&self) -> Value {
+        json!({
+            "day": self.day,
+            "part": self.part,
+            "answer": self.answer.to_json(),
+            "outcome": self.outcome.name(),
+            "time": self.time,
+            "wait": self.wait,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        Some(Attempt {
+            day: value["day"].as_u64()? as u8,
+            part: value["part"].as_u64()? as u8,
+            answer: match &value["answer"] {
+                Value::Number(number) => Answer::Number(number.as_i64()?),
+                Value::String(text) => Answer::Text(text.clone()),
+                _ => return None,
+            },
+            outcome: Outcome::from_name(value["outcome"].as_str()?)?,
+            time: value["time"].as_u64()?,
+            wait: value["wait"].as_u64()?,
+        })
+    }
+}
+
+/// Every submission made so far, oldest first.
+#[derive(Debug, Default, PartialEq)]
+pub struct History(pub Vec<Attempt>);
+
+impl History {
+    /// An empty history when the file does not exist yet.
+    pub fn read(path: &Path) -> Result<Self> {
+        let name = path.display().to_string();
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(History::default()),
+            Err(source) => return Err(Error::Io { name, source }),
+        };
+
+        let invalid = |message: &str| Error::Answers {
+            name: name.clone(),
+            message: message.to_string(),
+        };
+
+        let values: Vec<Value> =
+            serde_json::from_str(&text).map_err(|error| invalid(&error.to_string()))?;
+
+        values
+            .iter()
+            .map(|value| Attempt::from_json(value).ok_or_else(|| invalid("invalid submission")))
+            .collect::<Result<_>>()
+            .map(History)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let values: Vec<_> = self.0.iter().map(Attempt::to_json).collect();
+        let text = serde_json::to_string_pretty(&values).unwrap() + "\n";
+
+        fs::write(path, text).map_err(|source| Error::Io {
+            name: path.display().to_string(),
+            source,
+        })
+    }
+
+    fn attempts(&self, day: u8, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.0
+            .iter()
+            .filter(move |attempt| attempt.day == day && attempt.part == part)
+    }
+
+    /// Checks a new answer against the earlier submissions. Answers that are
+    /// known to be wrong, or that would be sent before the server allows it,
+    /// are refused. Numeric answers outside the bounds the server gave for
+    /// earlier ones are allowed, but come back as warnings.
+    pub fn check(&self, day: u8, part: u8, answer: &Answer, now: u64) -> Result<Vec<String>> {
+        let refuse = |message: String| Err(Error::Submission(message));
+
+        if let Some(attempt) = self
+            .attempts(day, part)
+            .find(|attempt| attempt.outcome == Outcome::Right)
+        {
+            return refuse(format!(
+                "day {} part {} was already solved with {}",
+                day, part, attempt.answer
+            ));
+        }
+
+        if let Some(attempt) = self
+            .attempts(day, part)
+            .find(|attempt| attempt.outcome.is_wrong() && attempt.answer == *answer)
+        {
+            return refuse(format!(
+                "{} was already submitted and was {}",
+                answer, attempt.outcome
+            ));
+        }
+
+        if let Some(allowed) = self
+            .0
+            .iter()
+            .map(|attempt| attempt.time + attempt.wait)
+            .max()
+        {
+            if allowed > now {
+                return refuse(format!(
+                    "the server asked to wait {}s more before submitting again",
+                    allowed - now
+                ));
+            }
+        }
+
+        let mut warnings = vec![];
+
+        if let Answer::Number(number) = *answer {
+            let bound = |outcome| {
+                self.attempts(day, part)
+                    .filter(move |attempt| attempt.outcome == outcome)
+                    .filter_map(|attempt| match attempt.answer {
+                        Answer::Number(number) => Some(number),
+                        _ => None,
+                    })
+            };
+
+            if let Some(high) = bound(Outcome::TooHigh).min().filter(|&high| number >= high) {
+                warnings.push(format!(
+                    "{} is not below {}, which was too high",
+                    number, high
+                ));
+            }
+            if let Some(low) = bound(Outcome::TooLow).max().filter(|&low| number <= low) {
+                warnings.push(format!(
+                    "{} is not above {}, which was too low",
+                    number, low
+                ));
+            }
+        }
+
+        Ok(warnings)
+    }
+}
+
+#[test]
+fn test_parse_response() {
+    assert_eq!(
+        parse_response("<p>That's the right answer! You are one gold star closer.</p>"),
+        Some((Outcome::Right, 0))
+    );
+    assert_eq!(
+        parse_response(
+            "<p>That's not the right answer; your answer is too high. Please wait one minute \
+             before trying again.</p>"
+        ),
+        Some((Outcome::TooHigh, 60))
+    );
+    assert_eq!(
+        parse_response(
+            "<p>That's not the right answer; your answer is too low. Because you have guessed \
+             incorrectly 4 times on this puzzle, please wait 5 minutes before trying again.</p>"
+        ),
+        Some((Outcome::TooLow, 300))
+    );
+    assert_eq!(
+        parse_response("<p>That's not the right answer.</p>"),
+        Some((Outcome::Wrong, 60))
+    );
+    assert_eq!(
+        parse_response("<p>You gave an answer too recently. You have 1m 12s left to wait.</p>"),
+        Some((Outcome::RateLimited, 72))
+    );
+    assert_eq!(
+        parse_response("<p>You don't seem to be solving the right level.</p>"),
+        Some((Outcome::AlreadySolved, 0))
+    );
+    assert_eq!(parse_response("<p>Welcome!</p>"), None);
+}
+
+#[test]
+fn test_check() {
+    let attempt = |answer: i64, outcome, time| Attempt {
+        day: 1,
+        part: 1,
+        answer: Answer::Number(answer),
+        outcome,
+        time,
+        wait: 60,
+    };
+    let history = History(vec![
+        attempt(500, Outcome::TooHigh, 1000),
+        attempt(100, Outcome::TooLow, 1100),
+    ]);
+
+    assert!(matches!(
+        history.check(1, 1, &Answer::Number(500), 2000),
+        Err(Error::Submission(_))
+    ));
+    assert_eq!(
+        history
+            .check(1, 1, &Answer::Number(300), 1130)
+            .unwrap_err()
+            .to_string(),
+        "the server asked to wait 30s more before submitting again"
+    );
+    assert_eq!(
+        history.check(1, 1, &Answer::Number(300), 2000).unwrap(),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        history.check(1, 1, &Answer::Number(600), 2000).unwrap(),
+        vec!["600 is not below 500, which was too high"]
+    );
+    assert_eq!(
+        history.check(1, 1, &Answer::Number(50), 2000).unwrap(),
+        vec!["50 is not above 100, which was too low"]
+    );
+    assert!(history.check(1, 2, &Answer::Number(500), 2000).is_ok());
+
+    let solved = History(vec![attempt(300, Outcome::Right, 1000)]);
+    assert_eq!(
+        solved
+            .check(1, 1, &Answer::Number(301), 2000)
+            .unwrap_err()
+            .to_string(),
+        "day 1 part 1 was already solved with 300"
+    );
+}
+
+#[test]
+fn test_history_round_trip() {
+    let path = std::env::temp_dir().join(format!("aoc2022-history-{}.json", std::process::id()));
+    let history = History(vec![Attempt {
+        day: 5,
+        part: 2,
+        answer: Answer::Text("MCD".to_string()),
+        outcome: Outcome::Wrong,
+        time: 1670000000,
+        wait: 60,
+    }]);
+
+    assert_eq!(History::read(&path).unwrap(), History::default());
+    history.write(&path).unwrap();
+    assert_eq!(History::read(&path).unwrap(), history);
+
+    fs::remove_file(path).unwrap();
+}
@@ -1,30 +1,43 @@
+use std::ops::Add;
+
 pub struct SummedGroups<I> {
     iter: I,
 }
 
-impl<I> Iterator for SummedGroups<I>
+impl<I, T> Iterator for SummedGroups<I>
 where
-    I: Iterator<Item = i32>,
+    I: Iterator<Item = Option<T>>,
+    T: Default + Add<Output = T>,
 {
-    type Item = I::Item;
+    type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let sum = self.iter.by_ref().take_while(|x| *x != 0).sum();
-        if sum > 0 {
-            Some(sum)
-        } else {
-            None
+        let mut sum = match self.iter.next()? {
+            Some(value) => value,
+            None => return Some(T::default()),
+        };
+
+        for value in self.iter.by_ref() {
+            match value {
+                Some(value) => sum = sum + value,
+                None => break,
+            }
         }
+
+        Some(sum)
     }
 }
 
+/// Sums the values between `None` separators. Every separator ends a group,
+/// so consecutive separators make an empty group that sums to the default
+/// value, while a separator at the very end does not start a new group.
 pub trait SummedGroupsTrait<I> {
     fn summed_groups(self) -> SummedGroups<I>;
 }
 
-impl<I> SummedGroupsTrait<I> for I
+impl<I, T> SummedGroupsTrait<I> for I
 where
-    I: Iterator<Item = i32>,
+    I: Iterator<Item = Option<T>>,
 {
     fn summed_groups(self) -> SummedGroups<I> {
         SummedGroups { iter: self }
@@ -33,9 +46,27 @@ where
 
 #[test]
 fn test_summed_groups_trait() {
-    let calories = vec![100, 200, 0, 600];
+    let calories = vec![
+        Some(100),
+        Some(200),
+        None,
+        Some(0),
+        None,
+        None,
+        Some(600),
+        None,
+    ];
     let sums = calories.into_iter().summed_groups().collect::<Vec<i32>>();
 
-    assert_eq!(sums[0], 300);
-    assert_eq!(sums[1], 600);
+    assert_eq!(sums, vec![300, 0, 0, 600]);
+}
+
+#[test]
+fn test_summed_groups_generic() {
+    let sums = vec![Some(5_000_000_000u64), Some(1), None, Some(2)]
+        .into_iter()
+        .summed_groups()
+        .collect::<Vec<_>>();
+
+    assert_eq!(sums, vec![5_000_000_001, 2]);
 }
@@ -0,0 +1,45 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// Selects the `k` largest items of an iterator in a single pass, keeping no
+/// more than `k` of them in memory at a time.
+pub trait TopKTrait<I>
+where
+    I: Iterator,
+{
+    /// The `k` largest items, largest first. Fewer when the iterator runs out
+    /// before that.
+    fn top_k(self, k: usize) -> Vec<I::Item>;
+}
+
+impl<I> TopKTrait<I> for I
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    fn top_k(self, k: usize) -> Vec<I::Item> {
+        // Not pre-sized, as `k` may well be far larger than the iterator.
+        let mut heap = BinaryHeap::new();
+
+        for item in self {
+            heap.push(Reverse(item));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(item)| item)
+            .collect()
+    }
+}
+
+#[test]
+fn test_top_k_trait() {
+    let calories = [6000, 4000, 11000, 24000, 10000];
+
+    assert_eq!(calories.iter().top_k(3), vec![&24000, &11000, &10000]);
+    assert_eq!(calories.iter().top_k(10).len(), 5);
+    assert!(calories.iter().top_k(0).is_empty());
+    assert_eq!(calories.iter().top_k(usize::MAX).len(), 5);
+}
@@ -0,0 +1,168 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    error::{Error, Result},
+    input::InputSource,
+    registry,
+};
+
+/// Notices changes to a set of files by comparing their modification times
+/// between calls. A file that is missing counts as a state of its own, so
+/// creating or deleting it is a change too.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    stamps: Option<Vec<Option<SystemTime>>>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Watcher {
+            paths,
+            stamps: None,
+        }
+    }
+
+    /// Whether any file changed since the previous call. Always true on the
+    /// first call.
+    pub fn changed(&mut self) -> bool {
+        let stamps: Vec<_> = self
+            .paths
+            .iter()
+            .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+            .collect();
+
+        let changed = self.stamps.as_ref() != Some(&stamps);
+        self.stamps = Some(stamps);
+        changed
+    }
+}
+
+/// Builds and runs a day with its timings through cargo, so that changes to
+/// the solution are picked up.
+fn run_command(root: &Path, source: &InputSource, day: u8) -> Command {
+    let mut command = Command::new("cargo");
+    command
+        .arg("run")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(root.join("Cargo.toml"))
+        .arg("--")
+        .arg("run")
+        .arg(day.to_string())
+        .arg("--time");
+
+    match source {
+        InputSource::File(path) => command.arg("--input").arg(path),
+        InputSource::Dir(dir) => command.arg("--inputs-dir").arg(dir),
+        InputSource::Stdin => &mut command,
+    };
+
+    command
+}
+
+/// Runs `day` of the crate at `root` every time its module or its input
+/// changes, until interrupted.
+pub fn watch(root: &Path, source: &InputSource, day: u8, interval: Duration) -> Result<()> {
+    if registry::find(day).is_none() {
+        return Err(Error::UnknownDay(day));
+    }
+
+    let input = match source {
+        InputSource::File(path) => path.clone(),
+        InputSource::Dir(dir) => dir.join(crate::input::file_name(day)),
+        InputSource::Stdin => {
+            return Err(Error::Usage(
+                "stdin cannot be watched, use --input or --inputs-dir".to_string(),
+            ))
+        }
+    };
+
+    let module = root.join("src").join(format!("day{}.rs", day));
+    let mut watcher = Watcher::new(vec![module.clone(), input.clone()]);
+
+    println!(
+        "Watching {} and {}, press Ctrl-C to stop",
+        module.display(),
+        input.display()
+    );
+
+    loop {
+        if watcher.changed() {
+            println!("\n--- day {} ---", day);
+
+            // Build and parse errors are printed by the child, and are no
+            // reason to stop watching.
+            if let Err(error) = run_command(root, source, day).status() {
+                eprintln!("error: could not run cargo: {}", error);
+            }
+        }
+
+        thread::sleep(interval);
+    }
+}
+
+#[test]
+fn test_watcher() {
+    let path = std::env::temp_dir().join(format!("aoc2022-watch-{}.txt", std::process::id()));
+    let mut watcher = Watcher::new(vec![path.clone()]);
+
+    assert!(watcher.changed());
+    assert!(!watcher.changed());
+
+    fs::write(&path, "1").unwrap();
+    assert!(watcher.changed());
+    assert!(!watcher.changed());
+
+    let file = fs::File::options().write(true).open(&path).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(10))
+        .unwrap();
+    assert!(watcher.changed());
+
+    fs::remove_file(&path).unwrap();
+    assert!(watcher.changed());
+}
+
+#[test]
+fn test_run_command() {
+    let command = run_command(Path::new("aoc"), &InputSource::Dir("inputs".into()), 7);
+    let args: Vec<_> = command
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect();
+
+    assert_eq!(
+        args,
+        [
+            "run",
+            "--quiet",
+            "--manifest-path",
+            "aoc/Cargo.toml",
+            "--",
+            "run",
+            "7",
+            "--time",
+            "--inputs-dir",
+            "inputs"
+        ]
+    );
+}
+
+#[test]
+fn test_watch_errors() {
+    let interval = Duration::from_millis(1);
+
+    assert!(matches!(
+        watch(Path::new("."), &InputSource::Stdin, 1, interval),
+        Err(Error::Usage(_))
+    ));
+    assert!(matches!(
+        watch(Path::new("."), &InputSource::default(), 26, interval),
+        Err(Error::UnknownDay(26))
+    ));
+}
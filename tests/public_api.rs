@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use aoc2022::{
+    day15::Day15,
+    group_by::GroupByTrait,
+    input::{Input, InputSource},
+    registry,
+    solution::{Answer, Solution},
+};
+
+fn read_example(day: u8) -> Input {
+    InputSource::Dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures"))
+        .read(day)
+        .unwrap()
+}
+
+#[test]
+fn test_registered_solution() {
+    let day1 = registry::find(1).unwrap();
+
+    assert_eq!(
+        day1.run(&read_example(1), 1).unwrap(),
+        Answer::Number(24000)
+    );
+}
+
+#[test]
+fn test_solution_with_parameters() {
+    let day = Day15 {
+        row: 10,
+        search_limit: 20,
+    };
+
+    assert_eq!(
+        day.part_1(&day.parse(&read_example(15)).unwrap()).unwrap(),
+        26
+    );
+}
+
+#[test]
+fn test_helpers() {
+    let groups: Vec<_> = "abcdef".chars().group_by(2).collect();
+
+    assert_eq!(groups, vec![vec!['a', 'b'], vec!['c', 'd'], vec!['e', 'f']]);
+}